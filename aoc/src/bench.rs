@@ -0,0 +1,216 @@
+//! Timing the parse and each part of the solutions over several runs.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::input;
+use aoc_common::parallel;
+
+use crate::cli::BenchArgs;
+use crate::days;
+use crate::params::Overrides;
+
+/// Timings of one phase of a day over every iteration.
+struct Row {
+    day: u8,
+    phase: String,
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+impl Row {
+    fn new(day: u8, phase: String, mut times: Vec<Duration>) -> Row {
+        times.sort();
+        Row {
+            day,
+            phase,
+            min: times[0],
+            median: times[times.len() / 2],
+            max: times[times.len() - 1],
+        }
+    }
+}
+
+/// Formats `d` with three significant digits in the largest fitting unit.
+fn fmt_duration(d: Duration) -> String {
+    let ns = d.as_nanos() as f64;
+    let (v, unit) = if ns < 1e3 {
+        (ns, "ns")
+    } else if ns < 1e6 {
+        (ns / 1e3, "µs")
+    } else if ns < 1e9 {
+        (ns / 1e6, "ms")
+    } else {
+        (ns / 1e9, "s")
+    };
+    let decimals = if v < 10.0 {
+        2
+    } else if v < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{v:.decimals$}{unit}")
+}
+
+fn print_table(rows: &[Row]) {
+    println!(
+        "{:<3}  {:<6}  {:>9}  {:>9}  {:>9}",
+        "day", "phase", "min", "median", "max"
+    );
+    for r in rows {
+        println!(
+            "{:<3}  {:<6}  {:>9}  {:>9}  {:>9}",
+            r.day,
+            r.phase,
+            fmt_duration(r.min),
+            fmt_duration(r.median),
+            fmt_duration(r.max)
+        );
+    }
+}
+
+/// Appends `rows` to the CSV at `path`, writing the header if it's a new file,
+/// so runs from different commits can be collected in one file.
+fn write_csv(path: &Path, rows: &[Row], iterations: usize, label: &str) -> io::Result<()> {
+    let new = !path.exists();
+    let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(
+            f,
+            "timestamp,label,day,phase,iterations,min_ns,median_ns,max_ns"
+        )?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    for r in rows {
+        writeln!(
+            f,
+            "{timestamp},{label},{},{},{iterations},{},{},{}",
+            r.day,
+            r.phase,
+            r.min.as_nanos(),
+            r.median.as_nanos(),
+            r.max.as_nanos()
+        )?;
+    }
+    Ok(())
+}
+
+pub fn bench(args: &BenchArgs) -> ExitCode {
+    let overrides = match Overrides::load(&args.params, &args.days) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // days one after the other, so only their inner loops share the threads
+    parallel::set_threads(args.jobs);
+    let mut ok = true;
+    let mut rows = vec![];
+
+    for day in args.days.clone() {
+        let entry = days::get(day);
+
+        let Some(parts) = entry.select(args.part) else {
+            if args.days.start() == args.days.end() {
+                eprintln!("error: day {day} has no part {}", args.part.unwrap());
+                return ExitCode::FAILURE;
+            }
+            continue;
+        };
+
+        let instr = match input::read(day, None, &[]) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("error: day {day}: {e}");
+                ok = false;
+                continue;
+            }
+        };
+
+        let mut parse_times = vec![];
+        let mut part_times = vec![vec![]; parts.len()];
+        for _ in 0..args.iterations {
+            let solved = match (entry.solve)(&instr, &parts, &overrides.get(day)) {
+                Ok(solved) => solved,
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    ok = false;
+                    break;
+                }
+            };
+            parse_times.push(solved.parse_time);
+            for (times, t) in part_times.iter_mut().zip(solved.part_times) {
+                times.push(t);
+            }
+        }
+        if parse_times.len() < args.iterations {
+            continue;
+        }
+
+        rows.push(Row::new(day, "parse".to_string(), parse_times));
+        for (part, times) in parts.iter().zip(part_times) {
+            rows.push(Row::new(day, format!("part{part}"), times));
+        }
+    }
+
+    print_table(&rows);
+
+    if let Some(path) = &args.csv {
+        if let Err(e) = write_csv(path, &rows, args.iterations, &args.label) {
+            eprintln!("error: cannot write {}: {e}", path.display());
+            ok = false;
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        let ms = Duration::from_millis;
+        let row = Row::new(3, "part1".to_string(), vec![ms(5), ms(1), ms(9), ms(2)]);
+        assert_eq!((row.min, row.median, row.max), (ms(1), ms(5), ms(9)));
+
+        assert_eq!(fmt_duration(Duration::from_nanos(950)), "950ns");
+        assert_eq!(fmt_duration(Duration::from_micros(1500)), "1.50ms");
+        assert_eq!(fmt_duration(Duration::from_millis(25_300)), "25.3s");
+    }
+
+    #[test]
+    fn csv() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let row = |day| Row::new(day, "parse".to_string(), vec![Duration::from_nanos(7)]);
+        write_csv(&path, &[row(1)], 1, "a").unwrap();
+        write_csv(&path, &[row(2)], 1, "b").unwrap();
+
+        // the header is only written once, then rows are appended
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "timestamp,label,day,phase,iterations,min_ns,median_ns,max_ns"
+        );
+        assert!(lines[1].ends_with(",a,1,parse,1,7,7,7"));
+        assert!(lines[2].ends_with(",b,2,parse,1,7,7,7"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
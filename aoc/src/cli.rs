@@ -0,0 +1,808 @@
+//! Command line parsing for the runner.
+
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use aoc_common::input::Source;
+use aoc_common::parallel;
+use aoc_common::toml::{self, Table};
+use aoc_common::trace::Filter;
+
+use crate::client;
+
+pub const USAGE: &str = "\
+Usage: aoc run [--day N|A-B|all] [--part 1|2] [--input PATH|-]
+               [--format text|json] [--jobs N|max] [PARAMS]
+       aoc verify [--day N|A-B|all] [--answers PATH] [--jobs N|max]
+       aoc bench [--day N|A-B|all] [--part 1|2] [--iterations N]
+                 [--csv PATH] [--label TEXT] [--jobs N|max] [PARAMS]
+       aoc render --day N [--input PATH|-] [--format ascii|ansi|ppm]
+                  [--output PATH] [--scale N] [PARAMS]
+       aoc trace --day N [--part 1|2] [--input PATH|-] [--event KIND]...
+                 [--where NAME=VALUE]... [--limit N] [--format text|json]
+                 [--output PATH] [PARAMS]
+       aoc params [--day N|A-B|all] [PARAMS]
+       aoc gen --day N [--seed N] [--size N] [--output PATH]
+       aoc fetch [--day N|A-B|all] [REMOTE]
+       aoc submit --day N --part 1|2 [--answer TEXT] [--input PATH|-]
+                  [REMOTE] [PARAMS]
+       aoc serve [--port N] [--answers PATH] [--session TOKEN]
+                 [--penalty SECONDS] [--year N]
+
+       PARAMS: [--config PATH] [--param NAME=VALUE]...
+       REMOTE: [--server URL] [--year N] [--state PATH]
+
+Commands:
+    run             Solve the selected days and print the answers, and what
+                    each part allocated when built with the `count-allocs`
+                    feature (which solves one day and part at a time,
+                    whatever --jobs says)
+    verify          Check the answers against the manifest of known answers
+    bench           Time the parse and each part over several iterations
+    render          Draw what a day's solution does (days 10, 14, 16, 17, 18,
+                    21 and 23)
+    trace           Follow what a day's simulation does step by step, with
+                    the events it emits (days 14, 16, 20 and 22)
+    params          Print the puzzle parameters the days use, as a config
+                    file
+    gen             Make up a random input for a day, the same for the same
+                    seed and size
+    fetch           Download the inputs not on disk yet from the puzzle
+                    server, to $AOC_INPUTS/YYYY/dayNN.txt or
+                    inputs/YYYY/dayNN.txt in the workspace
+    submit          Send an answer, the day's own unless given, to the puzzle
+                    server, unless it's known to be wrong
+    serve           Stand in for the puzzle server on localhost, with the
+                    inputs on disk and the answers of the manifest
+    help            Print this message
+
+Options:
+    -d, --day       Day, inclusive range of days or `all` (default: all)
+    -p, --part      Only run the given part (default: both)
+    -i, --input     Input file or `-` for stdin, only valid for a single day
+                    (default: $AOC_INPUTS/dayNN.txt, then the fetched
+                    input, then dayNN/input in the workspace)
+    -f, --format    `text`, or `json` for one object per line and part with
+                    the day, part, answer, elapsed nanoseconds, allocations
+                    and error (default: text)
+                    When rendering, `ascii`, `ansi` for colours or `ppm` for
+                    an image (default: ascii)
+                    When tracing, `text`, or `json` for one object per line
+                    and event (default: text)
+    -a, --answers   Manifest of expected answers (default: answers.toml)
+    -j, --jobs      Threads to solve on, or `max` for one per CPU; run and
+                    verify solve several days at once, and the days with
+                    independent sub-problems split them up, the answers are
+                    the same either way (default: 1)
+    -n, --iterations
+                    Number of timed runs of each day (default: 10)
+    --csv           Append the timings to this CSV file
+    --label         Label of the CSV rows, e.g. a commit (default: none)
+    -o, --output    Write the picture, input or events to this file
+                    (default: stdout)
+    --scale         Size in pixels of each tile of an image (default: 4)
+    -e, --event     Only trace events of this kind, can be repeated (default:
+                    all the day's kinds)
+    -w, --where     Only trace events with this field value, can be repeated
+    --limit         Stop printing events after this many
+    -s, --seed      Seed of a generated input (default: 0)
+    --size          Rough size of a generated input, what it counts depends
+                    on the day (default: 100)
+    --server        Base URL of the puzzle server, `http://` only (default:
+                    $AOC_SERVER), the session token is read from $AOC_SESSION
+    --year          Year of the puzzles (default: 2023)
+    --state         File remembering the answers sent and the waits asked
+                    for (default: submissions.toml)
+    --answer        Answer to send instead of solving the part
+    --port          Port to serve on (default: 8023)
+    --session       Session token the stand-in server accepts (default: any)
+    --penalty       Seconds to wait after a wrong answer (default: 60)
+    -c, --config    Config file changing puzzle parameters, with a `[dayNN]`
+                    section of `name = value` lines per day
+    -P, --param     Change a puzzle parameter, only valid for a single day,
+                    overriding the config file
+";
+
+#[derive(Debug)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    Bench(BenchArgs),
+    Render(RenderArgs),
+    Trace(TraceArgs),
+    Params(ParamsArgs),
+    Gen(GenArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Serve(ServeArgs),
+    Help,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Ascii,
+    Ansi,
+    Ppm,
+}
+
+/// Changes to the puzzle parameters.
+#[derive(Debug, Default)]
+pub struct ParamArgs {
+    pub config: Option<PathBuf>,
+    /// Changes given one by one, for a single day.
+    pub overrides: Table,
+}
+
+impl ParamArgs {
+    /// Handles `arg` if it's one of the parameter options, taking its value
+    /// from `value`.
+    fn parse_arg(
+        &mut self,
+        arg: &str,
+        value: impl FnOnce() -> Result<String, String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "-c" | "--config" => self.config = Some(PathBuf::from(value()?)),
+            "-P" | "--param" => {
+                let param = value()?;
+                let (name, v) = param
+                    .split_once('=')
+                    .ok_or(format!("invalid parameter `{param}`, expected NAME=VALUE"))?;
+                let v = toml::parse_value(v.trim())
+                    .map_err(|_| format!("invalid value `{v}` for parameter `{name}`"))?;
+                self.overrides.insert(name.trim().to_string(), v);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn check(&self, days: &RangeInclusive<u8>) -> Result<(), String> {
+        if !self.overrides.is_empty() && days.start() != days.end() {
+            return Err("`--param` can only be used with a single day".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Where the puzzle server is and what was sent to it.
+#[derive(Debug)]
+pub struct RemoteArgs {
+    pub server: Option<String>,
+    pub year: u16,
+    pub state: PathBuf,
+}
+
+impl Default for RemoteArgs {
+    fn default() -> Self {
+        RemoteArgs {
+            server: None,
+            year: client::YEAR,
+            state: PathBuf::from("submissions.toml"),
+        }
+    }
+}
+
+fn parse_year(s: &str) -> Result<u16, String> {
+    match s.parse() {
+        Ok(y) if y >= 2015 => Ok(y),
+        _ => Err(format!("invalid year `{s}`")),
+    }
+}
+
+impl RemoteArgs {
+    /// Handles `arg` if it's one of the server options, taking its value
+    /// from `value`.
+    fn parse_arg(
+        &mut self,
+        arg: &str,
+        value: impl FnOnce() -> Result<String, String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--server" => self.server = Some(value()?),
+            "--year" => self.year = parse_year(&value()?)?,
+            "--state" => self.state = PathBuf::from(value()?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+#[derive(Debug)]
+pub struct RunArgs {
+    pub days: RangeInclusive<u8>,
+    pub part: Option<u8>,
+    pub input: Option<Source>,
+    pub format: Format,
+    pub jobs: usize,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct VerifyArgs {
+    pub days: RangeInclusive<u8>,
+    pub answers: PathBuf,
+    pub jobs: usize,
+}
+
+#[derive(Debug)]
+pub struct BenchArgs {
+    pub days: RangeInclusive<u8>,
+    pub part: Option<u8>,
+    pub iterations: usize,
+    pub csv: Option<PathBuf>,
+    pub label: String,
+    pub jobs: usize,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct RenderArgs {
+    pub day: u8,
+    pub input: Option<Source>,
+    pub format: RenderFormat,
+    pub output: Option<PathBuf>,
+    pub scale: usize,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct TraceArgs {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub input: Option<Source>,
+    pub filter: Filter,
+    pub limit: Option<usize>,
+    pub format: Format,
+    pub output: Option<PathBuf>,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct ParamsArgs {
+    pub days: RangeInclusive<u8>,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct GenArgs {
+    pub day: u8,
+    pub seed: u64,
+    pub size: usize,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct FetchArgs {
+    pub days: RangeInclusive<u8>,
+    pub remote: RemoteArgs,
+}
+
+#[derive(Debug)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub input: Option<Source>,
+    pub remote: RemoteArgs,
+    pub params: ParamArgs,
+}
+
+#[derive(Debug)]
+pub struct ServeArgs {
+    pub port: u16,
+    pub answers: PathBuf,
+    pub session: Option<String>,
+    pub penalty: u64,
+    pub year: u16,
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.parse() {
+        Ok(d) if (1..=25).contains(&d) => Ok(d),
+        _ => Err(format!(
+            "invalid day `{s}`, expected a number between 1 and 25"
+        )),
+    }
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>, String> {
+    if s == "all" {
+        return Ok(1..=25);
+    }
+    if let Some((a, b)) = s.split_once('-') {
+        let (a, b) = (parse_day(a)?, parse_day(b)?);
+        if a > b {
+            return Err(format!("invalid day range `{s}`"));
+        }
+        Ok(a..=b)
+    } else {
+        let d = parse_day(s)?;
+        Ok(d..=d)
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part `{s}`, expected 1 or 2")),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("invalid format `{s}`, expected `text` or `json`")),
+    }
+}
+
+fn parse_render_format(s: &str) -> Result<RenderFormat, String> {
+    match s {
+        "ascii" => Ok(RenderFormat::Ascii),
+        "ansi" => Ok(RenderFormat::Ansi),
+        "ppm" => Ok(RenderFormat::Ppm),
+        _ => Err(format!(
+            "invalid format `{s}`, expected `ascii`, `ansi` or `ppm`"
+        )),
+    }
+}
+
+fn parse_jobs(s: &str) -> Result<usize, String> {
+    match s {
+        "max" => Ok(parallel::available()),
+        _ => match s.parse() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("invalid number of jobs `{s}`")),
+        },
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut run = RunArgs {
+        days: 1..=25,
+        part: None,
+        input: None,
+        format: Format::Text,
+        jobs: 1,
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => run.days = parse_days(&value()?)?,
+            "-p" | "--part" => run.part = Some(parse_part(&value()?)?),
+            "-i" | "--input" => run.input = Some(Source::from_arg(&value()?)),
+            "-f" | "--format" => run.format = parse_format(&value()?)?,
+            "-j" | "--jobs" => run.jobs = parse_jobs(&value()?)?,
+            _ if run.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    if run.input.is_some() && run.days.start() != run.days.end() {
+        return Err("`--input` can only be used with a single day".to_string());
+    }
+    run.params.check(&run.days)?;
+    Ok(run)
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<VerifyArgs, String> {
+    let mut verify = VerifyArgs {
+        days: 1..=25,
+        answers: PathBuf::from("answers.toml"),
+        jobs: 1,
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => verify.days = parse_days(&value()?)?,
+            "-a" | "--answers" => verify.answers = PathBuf::from(value()?),
+            "-j" | "--jobs" => verify.jobs = parse_jobs(&value()?)?,
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    Ok(verify)
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut bench = BenchArgs {
+        days: 1..=25,
+        part: None,
+        iterations: 10,
+        csv: None,
+        label: String::new(),
+        jobs: 1,
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => bench.days = parse_days(&value()?)?,
+            "-p" | "--part" => bench.part = Some(parse_part(&value()?)?),
+            "-n" | "--iterations" => {
+                let n = value()?;
+                bench.iterations = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid number of iterations `{n}`")),
+                }
+            }
+            "--csv" => bench.csv = Some(PathBuf::from(value()?)),
+            "-j" | "--jobs" => bench.jobs = parse_jobs(&value()?)?,
+            "--label" => {
+                let label = value()?;
+                if label.contains([',', '"', '\n']) {
+                    return Err(format!(
+                        "invalid label `{label}`, it can't contain `,`, `\"` or newlines"
+                    ));
+                }
+                bench.label = label;
+            }
+            _ if bench.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    bench.params.check(&bench.days)?;
+    Ok(bench)
+}
+
+fn parse_render(mut args: impl Iterator<Item = String>) -> Result<RenderArgs, String> {
+    let mut day = None;
+    let mut render = RenderArgs {
+        day: 0,
+        input: None,
+        format: RenderFormat::Ascii,
+        output: None,
+        scale: 4,
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value()?)?),
+            "-i" | "--input" => render.input = Some(Source::from_arg(&value()?)),
+            "-f" | "--format" => render.format = parse_render_format(&value()?)?,
+            "-o" | "--output" => render.output = Some(PathBuf::from(value()?)),
+            "--scale" => {
+                let n = value()?;
+                render.scale = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid scale `{n}`")),
+                }
+            }
+            _ if render.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    render.day = day.ok_or("`render` needs a `--day`")?;
+    Ok(render)
+}
+
+fn parse_trace(mut args: impl Iterator<Item = String>) -> Result<TraceArgs, String> {
+    let mut day = None;
+    let mut trace = TraceArgs {
+        day: 0,
+        part: None,
+        input: None,
+        filter: Filter::default(),
+        limit: None,
+        format: Format::Text,
+        output: None,
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value()?)?),
+            "-p" | "--part" => trace.part = Some(parse_part(&value()?)?),
+            "-i" | "--input" => trace.input = Some(Source::from_arg(&value()?)),
+            "-e" | "--event" => trace.filter.kinds.push(value()?),
+            "-w" | "--where" => {
+                let field = value()?;
+                let (name, v) = field
+                    .split_once('=')
+                    .ok_or(format!("invalid field `{field}`, expected NAME=VALUE"))?;
+                trace
+                    .filter
+                    .fields
+                    .push((name.trim().to_string(), v.trim().to_string()));
+            }
+            "--limit" => {
+                let n = value()?;
+                trace.limit = match n.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("invalid limit `{n}`")),
+                }
+            }
+            "-f" | "--format" => trace.format = parse_format(&value()?)?,
+            "-o" | "--output" => trace.output = Some(PathBuf::from(value()?)),
+            _ if trace.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    trace.day = day.ok_or("`trace` needs a `--day`")?;
+    Ok(trace)
+}
+
+fn parse_params(mut args: impl Iterator<Item = String>) -> Result<ParamsArgs, String> {
+    let mut params = ParamsArgs {
+        days: 1..=25,
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => params.days = parse_days(&value()?)?,
+            _ if params.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    params.params.check(&params.days)?;
+    Ok(params)
+}
+
+fn parse_gen(mut args: impl Iterator<Item = String>) -> Result<GenArgs, String> {
+    let mut day = None;
+    let mut gen = GenArgs {
+        day: 0,
+        seed: 0,
+        size: 100,
+        output: None,
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value()?)?),
+            "-s" | "--seed" => {
+                let n = value()?;
+                gen.seed = n.parse().map_err(|_| format!("invalid seed `{n}`"))?;
+            }
+            "--size" => {
+                let n = value()?;
+                gen.size = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid size `{n}`")),
+                }
+            }
+            "-o" | "--output" => gen.output = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    gen.day = day.ok_or("`gen` needs a `--day`")?;
+    Ok(gen)
+}
+
+fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<FetchArgs, String> {
+    let mut fetch = FetchArgs {
+        days: 1..=25,
+        remote: RemoteArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => fetch.days = parse_days(&value()?)?,
+            _ if fetch.remote.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    Ok(fetch)
+}
+
+fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<SubmitArgs, String> {
+    let (mut day, mut part) = (None, None);
+    let mut submit = SubmitArgs {
+        day: 0,
+        part: 0,
+        answer: None,
+        input: None,
+        remote: RemoteArgs::default(),
+        params: ParamArgs::default(),
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&value()?)?),
+            "-p" | "--part" => part = Some(parse_part(&value()?)?),
+            "--answer" => submit.answer = Some(value()?),
+            "-i" | "--input" => submit.input = Some(Source::from_arg(&value()?)),
+            _ if submit.remote.parse_arg(&arg, &mut value)? => {}
+            _ if submit.params.parse_arg(&arg, &mut value)? => {}
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    submit.day = day.ok_or("`submit` needs a `--day`")?;
+    submit.part = part.ok_or("`submit` needs a `--part`")?;
+    Ok(submit)
+}
+
+fn parse_serve(mut args: impl Iterator<Item = String>) -> Result<ServeArgs, String> {
+    let mut serve = ServeArgs {
+        port: 8023,
+        answers: PathBuf::from("answers.toml"),
+        session: None,
+        penalty: 60,
+        year: client::YEAR,
+    };
+
+    let mut seen = vec![];
+    while let Some(arg) = args.next() {
+        once(&mut seen, &arg)?;
+        let mut value = || args.next().ok_or(format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "--port" => {
+                let n = value()?;
+                serve.port = n.parse().map_err(|_| format!("invalid port `{n}`"))?;
+            }
+            "-a" | "--answers" => serve.answers = PathBuf::from(value()?),
+            "--session" => serve.session = Some(value()?),
+            "--penalty" => {
+                let n = value()?;
+                serve.penalty = n.parse().map_err(|_| format!("invalid penalty `{n}`"))?;
+            }
+            "--year" => serve.year = parse_year(&value()?)?,
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+    Ok(serve)
+}
+
+/// Long name of each short flag.
+const SHORT: [(&str, &str); 13] = [
+    ("-d", "--day"),
+    ("-p", "--part"),
+    ("-i", "--input"),
+    ("-f", "--format"),
+    ("-a", "--answers"),
+    ("-j", "--jobs"),
+    ("-n", "--iterations"),
+    ("-o", "--output"),
+    ("-e", "--event"),
+    ("-w", "--where"),
+    ("-s", "--seed"),
+    ("-c", "--config"),
+    ("-P", "--param"),
+];
+
+/// Flags that can be given more than once, each adding to the others.
+const REPEATABLE: [&str; 3] = ["--param", "--event", "--where"];
+
+/// Records the flag `arg` of a command as it's read, an error if it was
+/// given before under either of its names.
+fn once(seen: &mut Vec<String>, arg: &str) -> Result<(), String> {
+    let long = SHORT
+        .iter()
+        .find(|(short, _)| *short == arg)
+        .map_or(arg, |(_, long)| long);
+    if REPEATABLE.contains(&long) {
+        return Ok(());
+    }
+    if seen.iter().any(|s| s == long) {
+        return Err(format!("`{long}` given more than once"));
+    }
+    seen.push(long.to_string());
+    Ok(())
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => Ok(Command::Run(parse_run(args)?)),
+        Some("verify") => Ok(Command::Verify(parse_verify(args)?)),
+        Some("bench") => Ok(Command::Bench(parse_bench(args)?)),
+        Some("render") => Ok(Command::Render(parse_render(args)?)),
+        Some("trace") => Ok(Command::Trace(parse_trace(args)?)),
+        Some("params") => Ok(Command::Params(parse_params(args)?)),
+        Some("gen") => Ok(Command::Gen(parse_gen(args)?)),
+        Some("fetch") => Ok(Command::Fetch(parse_fetch(args)?)),
+        Some("submit") => Ok(Command::Submit(parse_submit(args)?)),
+        Some("serve") => Ok(Command::Serve(parse_serve(args)?)),
+        Some("help" | "-h" | "--help") | None => Ok(Command::Help),
+        Some(cmd) => Err(format!("unknown command `{cmd}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn commands() {
+        let Ok(Command::Run(run)) = parse(args("run -d 3-5 --part 2 -j 2 -f json")) else {
+            panic!("not a run");
+        };
+        assert_eq!(run.days, 3..=5);
+        assert_eq!((run.part, run.jobs, run.format), (Some(2), 2, Format::Json));
+        assert!(matches!(parse(args("")), Ok(Command::Help)));
+        assert_eq!(
+            parse(args("walk -d 1")).unwrap_err(),
+            "unknown command `walk`"
+        );
+    }
+
+    #[test]
+    fn bad_flags() {
+        assert_eq!(
+            parse(args("run --days 3")).unwrap_err(),
+            "unexpected argument `--days`"
+        );
+        assert_eq!(
+            parse(args("verify -d 3 -p 1")).unwrap_err(),
+            "unexpected argument `-p`"
+        );
+        assert_eq!(parse(args("run -d")).unwrap_err(), "missing value for `-d`");
+        assert_eq!(
+            parse(args("run -d 26")).unwrap_err(),
+            "invalid day `26`, expected a number between 1 and 25"
+        );
+        assert_eq!(
+            parse(args("run -i x.txt")).unwrap_err(),
+            "`--input` can only be used with a single day"
+        );
+    }
+
+    #[test]
+    fn repeated_flags() {
+        assert_eq!(
+            parse(args("run -d 3 --day 5")).unwrap_err(),
+            "`--day` given more than once"
+        );
+        assert_eq!(
+            parse(args("bench --csv a.csv --csv b.csv")).unwrap_err(),
+            "`--csv` given more than once"
+        );
+        // values that look like flags aren't counted
+        assert!(parse(args("run -d 3 -i - -p 1")).is_ok());
+        assert!(parse(args("run -d 17 -P max_run=2 --param ultra_max_run=9")).is_ok());
+        assert!(parse(args("trace -d 20 -e pulse -e low -w to=rx -w high=false")).is_ok());
+    }
+
+    #[test]
+    fn gen_sizes() {
+        assert_eq!(parse_gen(args("-d 5 --size 1")).unwrap().size, 1);
+        assert_eq!(
+            parse_gen(args("-d 5 --size 0")).unwrap_err(),
+            "invalid size `0`"
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! Client for the puzzle server: downloading inputs into the usual input
+//! paths and submitting answers.
+//!
+//! What the server said about each answer is kept in a state file, so an
+//! answer known to be wrong is never sent twice, a solved part isn't sent
+//! again, and the waits the server asks for are respected across runs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::input;
+use aoc_common::toml::{self, Document, Value};
+
+use crate::cli::{FetchArgs, RemoteArgs, SubmitArgs};
+use crate::days;
+use crate::http::{self, Response, Url};
+use crate::params::Overrides;
+
+/// Environment variable with the base URL of the server.
+pub const SERVER_VAR: &str = "AOC_SERVER";
+/// Environment variable with the session token to log in with.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Year of the puzzles solved here.
+pub const YEAR: u16 = input::YEAR;
+/// Least time between two requests to the server.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// What was submitted so far, read from sections like
+///
+/// ```toml
+/// [limits]
+/// last_request = 1701388800
+/// wait_until = 1701388860
+///
+/// [2023-day01]
+/// part1 = "54632"
+/// part2_wrong = "54018 54020"
+/// ```
+#[derive(Debug)]
+pub struct State {
+    path: PathBuf,
+    doc: Document,
+}
+
+fn section(year: u16, day: u8) -> String {
+    format!("{year}-day{day:02}")
+}
+
+impl State {
+    /// Reads the state file, empty if there's none yet.
+    pub fn load(path: &Path) -> Result<State, String> {
+        let doc = match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::parse(&s).map_err(|e| format!("{}: {}", path.display(), e.locate(&s)))?
+            }
+            Err(_) if !path.exists() => Document::new(),
+            Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+        };
+        Ok(State {
+            path: path.to_path_buf(),
+            doc,
+        })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, toml::write(&self.doc))
+            .map_err(|e| format!("cannot write {}: {e}", self.path.display()))
+    }
+
+    fn get(&self, section: &str, key: &str) -> Option<&Value> {
+        self.doc.get(section)?.get(key)
+    }
+
+    fn set(&mut self, section: &str, key: &str, value: Value) {
+        let table = self.doc.entry(section.to_string()).or_default();
+        table.insert(key.to_string(), value);
+    }
+
+    fn time(&self, key: &str) -> u64 {
+        match self.get("limits", key) {
+            Some(Value::Int(t)) => *t as u64,
+            _ => 0,
+        }
+    }
+
+    fn set_time(&mut self, key: &str, t: u64) {
+        self.set("limits", key, Value::Int(t as i128));
+    }
+
+    /// The accepted answer of a part, if any.
+    pub fn right(&self, year: u16, day: u8, part: u8) -> Option<String> {
+        self.get(&section(year, day), &format!("part{part}"))
+            .map(Value::to_string)
+    }
+
+    /// Answers of a part the server said are wrong.
+    pub fn wrong(&self, year: u16, day: u8, part: u8) -> Vec<String> {
+        match self.get(&section(year, day), &format!("part{part}_wrong")) {
+            Some(v) => v.to_string().split_whitespace().map(String::from).collect(),
+            None => vec![],
+        }
+    }
+
+    fn add_wrong(&mut self, year: u16, day: u8, part: u8, answer: &str) {
+        let mut wrong = self.wrong(year, day, part);
+        wrong.push(answer.to_string());
+        let key = format!("part{part}_wrong");
+        self.set(&section(year, day), &key, Value::Str(wrong.join(" ")));
+    }
+}
+
+/// What became of an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    /// Wrong, with the server's hint like `too high`, if any.
+    Wrong(Option<String>),
+    /// Not sent, it was wrong before.
+    KnownWrong,
+    /// Not sent, the part was solved with this answer before.
+    Solved(String),
+    /// The server or an earlier answer asks to wait this long.
+    TooSoon(Duration),
+    /// The part is solved or not open yet.
+    WrongLevel,
+}
+
+/// How long the server asks to wait, from phrases like `Please wait one
+/// minute`, `Please wait 30 seconds` or `You have 1m 5s left to wait`.
+fn wait_in(body: &str) -> Option<Duration> {
+    if let Some(rest) = body.split("Please wait ").nth(1) {
+        let mut words = rest.split_whitespace();
+        let n = match words.next()? {
+            "one" | "a" => 1,
+            n => n.parse().ok()?,
+        };
+        let secs = match words.next()? {
+            w if w.starts_with("minute") => 60 * n,
+            w if w.starts_with("second") => n,
+            _ => return None,
+        };
+        return Some(Duration::from_secs(secs));
+    }
+
+    let rest = body.split("You have ").nth(1)?;
+    let mut secs = 0;
+    for word in rest.split_whitespace().take_while(|&w| w != "left") {
+        secs += if let Some(n) = word.strip_suffix('m') {
+            60 * n.parse::<u64>().ok()?
+        } else if let Some(n) = word.strip_suffix('s') {
+            n.parse::<u64>().ok()?
+        } else {
+            return None;
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+pub struct Client {
+    url: Url,
+    session: String,
+    year: u16,
+    state: State,
+    /// Least time between two requests, kept across runs in the state.
+    pub interval: Duration,
+}
+
+impl Client {
+    pub fn new(url: Url, session: String, year: u16, state: State) -> Client {
+        Client {
+            url,
+            session,
+            year,
+            state,
+            interval: INTERVAL,
+        }
+    }
+
+    /// A client for the server and session in the environment, or given
+    /// on the command line.
+    pub fn from_args(args: &RemoteArgs) -> Result<Client, String> {
+        let server = match &args.server {
+            Some(server) => server.clone(),
+            None => std::env::var(SERVER_VAR)
+                .map_err(|_| format!("no server, pass `--server URL` or set ${SERVER_VAR}"))?,
+        };
+        let session = std::env::var(SESSION_VAR)
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(format!("no session token, set ${SESSION_VAR}"))?;
+        let state = State::load(&args.state)?;
+        Ok(Client::new(
+            Url::parse(&server)?,
+            session.trim().to_string(),
+            args.year,
+            state,
+        ))
+    }
+
+    fn send(&mut self, method: &str, path: &str, body: &str) -> Result<Response, String> {
+        let wait =
+            (self.state.time("last_request") + self.interval.as_secs()).saturating_sub(now());
+        thread::sleep(Duration::from_secs(wait));
+        self.state.set_time("last_request", now());
+        self.state.save()?;
+
+        let path = format!("/{}/day/{path}", self.year);
+        let cookie = format!("session={}", self.session);
+        let mut headers = vec![("Cookie", cookie.as_str())];
+        if method == "POST" {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+        }
+        let response = http::send(&self.url, method, &path, &headers, body)
+            .map_err(|e| format!("cannot reach {}:{}: {e}", self.url.host, self.url.port))?;
+        match response.status {
+            200 => Ok(response),
+            400 => Err("the server didn't accept the session token".to_string()),
+            404 => Err(format!("the server has no {path}")),
+            status => Err(format!("the server answered {status} to {path}")),
+        }
+    }
+
+    pub fn input(&mut self, day: u8) -> Result<String, String> {
+        Ok(self.send("GET", &format!("{day}/input"), "")?.body)
+    }
+
+    pub fn submit(&mut self, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(format!("invalid answer `{answer}`"));
+        }
+        if let Some(right) = self.state.right(self.year, day, part) {
+            return Ok(Verdict::Solved(right));
+        }
+        if self
+            .state
+            .wrong(self.year, day, part)
+            .iter()
+            .any(|w| w == answer)
+        {
+            return Ok(Verdict::KnownWrong);
+        }
+        let wait = self.state.time("wait_until").saturating_sub(now());
+        if wait > 0 {
+            return Ok(Verdict::TooSoon(Duration::from_secs(wait)));
+        }
+
+        let form = format!("level={part}&answer={}", http::form_encode(answer));
+        let body = self.send("POST", &format!("{day}/answer"), &form)?.body;
+
+        let verdict = if body.contains("That's the right answer") {
+            let section = section(self.year, day);
+            self.state
+                .set(&section, &format!("part{part}"), Value::Str(answer.into()));
+            Verdict::Right
+        } else if body.contains("That's not the right answer") {
+            self.state.add_wrong(self.year, day, part, answer);
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|h| body.contains(h))
+                .map(String::from);
+            Verdict::Wrong(hint)
+        } else if body.contains("You gave an answer too recently") {
+            Verdict::TooSoon(wait_in(&body).unwrap_or(Duration::ZERO))
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return Err("the server's answer makes no sense".to_string());
+        };
+
+        if let Some(wait) = wait_in(&body) {
+            self.state.set_time("wait_until", now() + wait.as_secs());
+        }
+        self.state.save()?;
+        Ok(verdict)
+    }
+}
+
+/// Downloads the inputs of the selected days that aren't on disk yet.
+pub fn fetch(args: &FetchArgs) -> ExitCode {
+    let mut client = match Client::from_args(&args.remote) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut ok = true;
+    for day in args.days.clone() {
+        let path = input::cache_path(args.remote.year, day);
+        if path.is_file() {
+            println!("day {day}: {} (cached)", path.display());
+            continue;
+        }
+        let res = client.input(day).and_then(|input| {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)
+                    .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+            }
+            fs::write(&path, input).map_err(|e| format!("cannot write {}: {e}", path.display()))
+        });
+        match res {
+            Ok(()) => println!("day {day}: {}", path.display()),
+            Err(e) => {
+                eprintln!("error: day {day}: {e}");
+                ok = false;
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Solves a part, unless the answer is given, and submits it.
+pub fn submit(args: &SubmitArgs) -> ExitCode {
+    let (day, part) = (args.day, args.part);
+    let answer = match &args.answer {
+        Some(answer) => Ok(answer.clone()),
+        None => Overrides::load(&args.params, &(day..=day)).and_then(|overrides| {
+            let input = input::read(day, args.input.as_ref(), &[])
+                .map_err(|e| format!("day {day}: {e}"))?;
+            let solved = (days::get(day).solve)(&input, &[part], &overrides.get(day))?;
+            Ok(solved.answers[0].to_string())
+        }),
+    };
+    let verdict = answer.and_then(|answer| {
+        println!("Day {day} part {part}: {answer}");
+        Client::from_args(&args.remote)?.submit(day, part, &answer)
+    });
+
+    let (message, ok) = match verdict {
+        Ok(Verdict::Right) => ("right".to_string(), true),
+        Ok(Verdict::Wrong(Some(hint))) => (format!("wrong, {hint}"), false),
+        Ok(Verdict::Wrong(None)) => ("wrong".to_string(), false),
+        Ok(Verdict::KnownWrong) => ("wrong before, not sent again".to_string(), false),
+        Ok(Verdict::Solved(right)) => {
+            let same = args.answer.as_ref().is_none_or(|a| *a == right);
+            (format!("already solved with {right}, not sent"), same)
+        }
+        Ok(Verdict::TooSoon(wait)) => (
+            format!("not sent, wait {}s before answering again", wait.as_secs()),
+            false,
+        ),
+        Ok(Verdict::WrongLevel) => ("the part is solved or not open yet".to_string(), false),
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{message}");
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::server::Server;
+    use crate::verify::Manifest;
+
+    /// A stand-in server on a free port, answering until the tests end.
+    fn start(penalty: Duration) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let inputs = BTreeMap::from([(1, "1abc2\n".to_string())]);
+        let answers = Manifest::parse("[day01]\npart1 = 12\npart2 = 34\n").unwrap();
+        let mut server = Server::new(inputs, answers);
+        server.session = Some("secret".to_string());
+        server.penalty = penalty;
+        thread::spawn(move || server.serve(listener));
+        Url::parse(&format!("http://127.0.0.1:{port}")).unwrap()
+    }
+
+    fn client(url: &Url, session: &str, name: &str) -> Client {
+        let path = std::env::temp_dir().join(format!("aoc-{}-{name}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut client = Client::new(
+            url.clone(),
+            session.to_string(),
+            YEAR,
+            State::load(&path).unwrap(),
+        );
+        client.interval = Duration::ZERO;
+        client
+    }
+
+    #[test]
+    fn fetch_and_submit() {
+        let url = start(Duration::ZERO);
+        let mut c = client(&url, "secret", "flow");
+        assert_eq!(c.input(1).unwrap(), "1abc2\n");
+        assert!(c.input(2).unwrap_err().contains("no /2023/day/2/input"));
+
+        assert_eq!(c.submit(1, 2, "34").unwrap(), Verdict::WrongLevel);
+        assert_eq!(
+            c.submit(1, 1, "13").unwrap(),
+            Verdict::Wrong(Some("too high".to_string()))
+        );
+        assert_eq!(c.submit(1, 1, "13").unwrap(), Verdict::KnownWrong);
+        assert_eq!(c.submit(1, 1, "12").unwrap(), Verdict::Right);
+        assert_eq!(
+            c.submit(1, 1, "11").unwrap(),
+            Verdict::Solved("12".to_string())
+        );
+        assert_eq!(c.submit(1, 2, "34").unwrap(), Verdict::Right);
+
+        // all of it is remembered in the state file
+        let state = State::load(&c.state.path).unwrap();
+        assert_eq!(state.right(YEAR, 1, 1).as_deref(), Some("12"));
+        assert_eq!(state.wrong(YEAR, 1, 1), ["13"]);
+        // and kept apart from other years
+        assert_eq!(state.right(YEAR - 1, 1, 1), None);
+        assert!(state.wrong(YEAR - 1, 1, 1).is_empty());
+        fs::remove_file(&c.state.path).unwrap();
+    }
+
+    #[test]
+    fn waits() {
+        let url = start(Duration::from_secs(60));
+        let mut a = client(&url, "secret", "wait-a");
+        assert_eq!(
+            a.submit(1, 1, "1").unwrap(),
+            Verdict::Wrong(Some("too low".to_string()))
+        );
+        // the wait is kept and checked before sending anything
+        assert!(matches!(a.submit(1, 1, "2").unwrap(), Verdict::TooSoon(d) if d.as_secs() > 50));
+
+        // the server makes a client that didn't know wait too
+        let mut b = client(&url, "secret", "wait-b");
+        assert!(matches!(b.submit(1, 1, "2").unwrap(), Verdict::TooSoon(d) if d.as_secs() > 50));
+        assert!(b.state.wrong(YEAR, 1, 1).is_empty());
+
+        let mut c = client(&url, "wrong", "wait-c");
+        assert!(c.input(1).unwrap_err().contains("session token"));
+        for c in [a, b, c] {
+            let _ = fs::remove_file(&c.state.path);
+        }
+    }
+
+    #[test]
+    fn wait_phrases() {
+        let secs = |s| wait_in(s).map(|d| d.as_secs());
+        assert_eq!(
+            secs("Please wait one minute before trying again."),
+            Some(60)
+        );
+        assert_eq!(
+            secs("Please wait 5 minutes before trying again."),
+            Some(300)
+        );
+        assert_eq!(
+            secs("Please wait 30 seconds before trying again."),
+            Some(30)
+        );
+        assert_eq!(secs("You have 1m 5s left to wait."), Some(65));
+        assert_eq!(secs("You have 34s left to wait."), Some(34));
+        assert_eq!(secs("That's the right answer!"), None);
+        assert_eq!(secs("You have 5é left to wait."), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Registry of every day's solutions.
+
+use aoc_common::params::{Params, Values};
+use aoc_common::render::Canvas;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{self, Solution, Solved};
+use aoc_common::toml::Table;
+
+pub struct Day {
+    pub day: u8,
+    pub parts: u8,
+    /// Parses an input and solves the given parts of it, with the given
+    /// parameters changed from their defaults.
+    pub solve: fn(&str, &[u8], &Table) -> Result<Solved, String>,
+    /// Parses an input and draws it, `None` if the day has no picture.
+    pub render: fn(&str, &Table) -> Result<Option<Canvas>, String>,
+    /// Every parameter with its value after the given changes.
+    pub params: fn(&Table) -> Result<Values, String>,
+    /// Makes up a random input of about the given size.
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// Kinds of the events the day can be traced with.
+    pub events: &'static [&'static str],
+}
+
+impl Day {
+    /// The parts to run when asked for `part`, or all of them, `None` if the
+    /// day doesn't have that part.
+    pub fn select(&self, part: Option<u8>) -> Option<Vec<u8>> {
+        match part {
+            Some(part) if part > self.parts => None,
+            Some(part) => Some(vec![part]),
+            None => Some((1..=self.parts).collect()),
+        }
+    }
+}
+
+fn params<S: Solution>(overrides: &Table) -> Result<S::Params, String> {
+    S::Params::with(overrides).map_err(|e| format!("day {}: {e}", S::DAY))
+}
+
+fn solve<S: Solution>(input: &str, parts: &[u8], overrides: &Table) -> Result<Solved, String> {
+    let params = params::<S>(overrides)?;
+    solution::solve::<S>(input, parts, &params).map_err(|e| e.to_string())
+}
+
+fn render<S: Solution>(input: &str, overrides: &Table) -> Result<Option<Canvas>, String> {
+    let params = params::<S>(overrides)?;
+    solution::render::<S>(input, &params).map_err(|e| e.to_string())
+}
+
+const fn entry<S: Solution>() -> Day {
+    Day {
+        day: S::DAY,
+        parts: S::PARTS,
+        solve: solve::<S>,
+        render: render::<S>,
+        params: |overrides| Ok(params::<S>(overrides)?.values()),
+        generate: S::generate,
+        events: S::EVENTS,
+    }
+}
+
+pub static DAYS: [Day; 25] = [
+    entry::<day01::Day01>(),
+    entry::<day02::Day02>(),
+    entry::<day03::Day03>(),
+    entry::<day04::Day04>(),
+    entry::<day05::Day05>(),
+    entry::<day06::Day06>(),
+    entry::<day07::Day07>(),
+    entry::<day08::Day08>(),
+    entry::<day09::Day09>(),
+    entry::<day10::Day10>(),
+    entry::<day11::Day11>(),
+    entry::<day12::Day12>(),
+    entry::<day13::Day13>(),
+    entry::<day14::Day14>(),
+    entry::<day15::Day15>(),
+    entry::<day16::Day16>(),
+    entry::<day17::Day17>(),
+    entry::<day18::Day18>(),
+    entry::<day19::Day19>(),
+    entry::<day20::Day20>(),
+    entry::<day21::Day21>(),
+    entry::<day22::Day22>(),
+    entry::<day23::Day23>(),
+    entry::<day24::Day24>(),
+    entry::<day25::Day25>(),
+];
+
+pub fn get(day: u8) -> &'static Day {
+    let entry = &DAYS[day as usize - 1];
+    debug_assert_eq!(entry.day, day, "DAYS is out of order");
+    entry
+}
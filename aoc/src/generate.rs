@@ -0,0 +1,49 @@
+//! Random inputs for trying the solutions on more than the puzzle input.
+
+use std::fs;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use aoc_common::rng::Rng;
+
+use crate::cli::GenArgs;
+use crate::days;
+
+pub fn generate(args: &GenArgs) -> ExitCode {
+    let input = (days::get(args.day).generate)(&mut Rng::new(args.seed), args.size);
+
+    let res = match &args.output {
+        Some(path) => {
+            fs::write(path, input).map_err(|e| format!("cannot write {}: {e}", path.display()))
+        }
+        None => io::stdout()
+            .lock()
+            .write_all(input.as_bytes())
+            .map_err(|e| e.to_string()),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::toml::Table;
+
+    use super::*;
+
+    #[test]
+    fn smallest_inputs_solve() {
+        for entry in &days::DAYS {
+            let input = (entry.generate)(&mut Rng::new(0), 1);
+            let parts: Vec<_> = (1..=entry.parts).collect();
+            if let Err(e) = (entry.solve)(&input, &parts, &Table::new()) {
+                panic!("{e}\n{input}");
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Just enough HTTP to talk to the puzzle server and to stand in for it:
+//! plain `http://` URLs, one request per connection and bodies sized by
+//! `Content-Length` or the end of the connection.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// How long to wait on a silent connection before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where a server is, from a URL like `http://localhost:8023/prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    pub host: String,
+    pub port: u16,
+    /// Path every request goes under, empty or starting with `/`.
+    pub prefix: String,
+}
+
+impl Url {
+    pub fn parse(s: &str) -> Result<Url, String> {
+        let rest = s.strip_prefix("http://").ok_or_else(|| {
+            if s.starts_with("https://") {
+                format!("invalid server `{s}`, only `http://` URLs are supported")
+            } else {
+                format!("invalid server `{s}`, expected a URL like `http://host:port`")
+            }
+        })?;
+        let (authority, prefix) = match rest.find('/') {
+            Some(i) => (&rest[..i], rest[i..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| format!("invalid port `{port}` in `{s}`"))?;
+                (host, port)
+            }
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(format!("invalid server `{s}`, missing a host"));
+        }
+        Ok(Url {
+            host: host.to_string(),
+            port,
+            prefix: prefix.to_string(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    /// Value of the header `name`, compared without case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Value of the cookie `name`.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("Cookie")?
+            .split(';')
+            .filter_map(|c| c.trim().split_once('='))
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v)
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, what.to_string())
+}
+
+/// Reads the header lines up to the blank line ending them.
+fn read_headers(r: &mut impl BufRead) -> io::Result<Vec<(String, String)>> {
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        r.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(headers);
+        }
+        let (name, value) = line.split_once(':').ok_or_else(|| invalid("bad header"))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+}
+
+/// Reads a body of `Content-Length` bytes, or everything left if there's
+/// no length.
+fn read_body(r: &mut impl Read, headers: &[(String, String)]) -> io::Result<String> {
+    let len = headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case("Content-Length"))
+        .map(|(_, v)| v.parse::<u64>().map_err(|_| invalid("bad content length")))
+        .transpose()?;
+    let mut body = vec![];
+    match len {
+        Some(len) => r.take(len).read_to_end(&mut body)?,
+        None => r.read_to_end(&mut body)?,
+    };
+    String::from_utf8(body).map_err(|_| invalid("body isn't UTF-8"))
+}
+
+/// Sends a request and waits for the whole response.
+pub fn send(
+    url: &Url,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> io::Result<Response> {
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    // HTTP/1.0 keeps servers from answering in chunks
+    let mut head = format!(
+        "{method} {}{path} HTTP/1.0\r\nHost: {}\r\nContent-Length: {}\r\n",
+        url.prefix,
+        url.host,
+        body.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+
+    let mut r = BufReader::new(stream);
+    let mut status_line = String::new();
+    r.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("bad status line"))?;
+    let headers = read_headers(&mut r)?;
+    let body = read_body(&mut r, &headers)?;
+    Ok(Response { status, body })
+}
+
+/// Reads a request from a client.
+pub fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut r = BufReader::new(stream);
+    let mut request_line = String::new();
+    r.read_line(&mut request_line)?;
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(invalid("bad request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let headers = read_headers(&mut r)?;
+    // a request without a length has no body, the client waits for us
+    let body = if headers
+        .iter()
+        .any(|(n, _)| n.eq_ignore_ascii_case("Content-Length"))
+    {
+        read_body(&mut r, &headers)?
+    } else {
+        String::new()
+    };
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+pub fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Unknown",
+    };
+    write!(
+        stream,
+        "HTTP/1.0 {} {reason}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Encodes `value` for a form body, keeping only letters, digits and `-._~`
+/// as they are.
+pub fn form_encode(value: &str) -> String {
+    let mut out = String::new();
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(b as char)
+            }
+            b' ' => out.push('+'),
+            b => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+/// The fields of a form body like `level=1&answer=42`, decoded.
+pub fn form_decode(body: &str) -> Vec<(String, String)> {
+    let decode = |s: &str| {
+        let mut bytes = vec![];
+        let mut iter = s.bytes();
+        while let Some(b) = iter.next() {
+            match b {
+                b'+' => bytes.push(b' '),
+                b'%' => {
+                    let hex: Vec<u8> = iter.by_ref().take(2).collect();
+                    let v = std::str::from_utf8(&hex)
+                        .ok()
+                        .and_then(|h| u8::from_str_radix(h, 16).ok());
+                    bytes.extend(v.map_or(hex, |v| vec![v]));
+                }
+                b => bytes.push(b),
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    body.split('&')
+        .filter(|f| !f.is_empty())
+        .map(|f| {
+            let (name, value) = f.split_once('=').unwrap_or((f, ""));
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls() {
+        let url = Url::parse("http://localhost:8023/aoc/").unwrap();
+        assert_eq!(
+            url,
+            Url {
+                host: "localhost".to_string(),
+                port: 8023,
+                prefix: "/aoc".to_string(),
+            }
+        );
+        assert_eq!(Url::parse("http://example.com").unwrap().port, 80);
+        assert!(Url::parse("https://example.com")
+            .unwrap_err()
+            .contains("only `http://`"));
+        assert!(Url::parse("http://host:port").is_err());
+        assert!(Url::parse("localhost:8023").is_err());
+    }
+
+    #[test]
+    fn forms() {
+        let body = format!("level=1&answer={}", form_encode("a b&c=-1%"));
+        assert_eq!(body, "level=1&answer=a+b%26c%3D-1%25");
+        assert_eq!(
+            form_decode(&body),
+            [
+                ("level".to_string(), "1".to_string()),
+                ("answer".to_string(), "a b&c=-1%".to_string())
+            ]
+        );
+    }
+}
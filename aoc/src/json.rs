@@ -0,0 +1,32 @@
+//! Just enough JSON writing for the machine-readable output.
+
+use std::fmt::Write;
+
+/// `s` as a quoted JSON string.
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// `s` as a JSON string, or `null`.
+pub fn opt_string(s: Option<&str>) -> String {
+    s.map_or("null".to_string(), string)
+}
+
+/// `n` as a JSON number, or `null`.
+pub fn opt_number(n: Option<u128>) -> String {
+    n.map_or("null".to_string(), |n| n.to_string())
+}
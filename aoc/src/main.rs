@@ -0,0 +1,150 @@
+use std::process::ExitCode;
+
+use aoc_common::input;
+use aoc_common::memory::{self, Usage};
+use aoc_common::parallel;
+use aoc_common::solution::Solved;
+
+mod bench;
+mod cli;
+mod client;
+mod days;
+mod generate;
+mod http;
+mod json;
+mod params;
+mod render;
+mod server;
+mod trace;
+mod verify;
+
+use cli::{Command, Format, RunArgs, USAGE};
+use params::Overrides;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOC: memory::Counting = memory::Counting;
+
+fn fmt_usage(usage: &Usage) -> String {
+    format!(
+        "{} allocs, {} allocated, {} peak",
+        usage.allocs,
+        memory::fmt_bytes(usage.bytes),
+        memory::fmt_bytes(usage.peak)
+    )
+}
+
+fn print_text(day: u8, parts: &[u8], res: &Result<Solved, String>) {
+    match res {
+        Ok(solved) => {
+            println!("Day {day}");
+            for (i, (part, answer)) in parts.iter().zip(&solved.answers).enumerate() {
+                match &solved.part_usage {
+                    Some(usage) => println!("Part {part}: {answer} ({})", fmt_usage(&usage[i])),
+                    None => println!("Part {part}: {answer}"),
+                }
+            }
+        }
+        Err(e) => eprintln!("error: {e}"),
+    }
+}
+
+/// One line per part, errors go in the `error` field instead of stderr.
+fn print_json(day: u8, parts: &[u8], res: &Result<Solved, String>) {
+    for (i, part) in parts.iter().enumerate() {
+        let (answer, elapsed, parse, usage, error) = match res {
+            Ok(solved) => (
+                Some(solved.answers[i].to_string()),
+                Some(solved.part_times[i].as_nanos()),
+                Some(solved.parse_time.as_nanos()),
+                solved.part_usage.as_ref().map(|usage| usage[i]),
+                None,
+            ),
+            Err(e) => (None, None, None, None, Some(e.as_str())),
+        };
+        println!(
+            "{{\"day\":{day},\"part\":{part},\"answer\":{},\"elapsed_ns\":{},\"parse_ns\":{},\"allocs\":{},\"alloc_bytes\":{},\"peak_bytes\":{},\"error\":{}}}",
+            json::opt_string(answer.as_deref()),
+            json::opt_number(elapsed),
+            json::opt_number(parse),
+            json::opt_number(usage.map(|u| u.allocs as u128)),
+            json::opt_number(usage.map(|u| u.bytes as u128)),
+            json::opt_number(usage.map(|u| u.peak as u128)),
+            json::opt_string(error),
+        );
+    }
+}
+
+fn run(args: &RunArgs) -> ExitCode {
+    let overrides = match Overrides::load(&args.params, &args.days) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.days.start() == args.days.end() {
+        let day = *args.days.start();
+        if days::get(day).select(args.part).is_none() {
+            eprintln!("error: day {day} has no part {}", args.part.unwrap());
+            return ExitCode::FAILURE;
+        }
+    }
+    let selected: Vec<(u8, Vec<u8>)> = args
+        .days
+        .clone()
+        .filter_map(|day| Some((day, days::get(day).select(args.part)?)))
+        .collect();
+
+    // the allocation counters are shared by every thread, so the parts are
+    // only measured alone when solved one at a time
+    let jobs = if cfg!(feature = "count-allocs") {
+        1
+    } else {
+        args.jobs
+    };
+    parallel::set_threads(jobs);
+    let results = parallel::stream(&selected, |(day, parts)| {
+        input::read(*day, args.input.as_ref(), &[])
+            .map_err(|e| format!("day {day}: {e}"))
+            .and_then(|instr| (days::get(*day).solve)(&instr, parts, &overrides.get(*day)))
+    });
+
+    let mut ok = true;
+    for ((day, parts), res) in selected.iter().zip(results) {
+        ok &= res.is_ok();
+        match args.format {
+            Format::Text => print_text(*day, parts, &res),
+            Format::Json => print_json(*day, parts, &res),
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    match cli::parse(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => run(&args),
+        Ok(Command::Verify(args)) => verify::verify(&args),
+        Ok(Command::Bench(args)) => bench::bench(&args),
+        Ok(Command::Render(args)) => render::render(&args),
+        Ok(Command::Trace(args)) => trace::trace(&args),
+        Ok(Command::Params(args)) => params::params(&args),
+        Ok(Command::Gen(args)) => generate::generate(&args),
+        Ok(Command::Fetch(args)) => client::fetch(&args),
+        Ok(Command::Submit(args)) => client::submit(&args),
+        Ok(Command::Serve(args)) => server::serve(&args),
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Puzzle parameters changed from a config file and the command line.
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::process::ExitCode;
+
+use aoc_common::toml::{self, Document, Table};
+
+use crate::cli::{ParamArgs, ParamsArgs};
+use crate::days;
+
+/// Parameter changes of each day, by section name.
+#[derive(Debug, Default)]
+pub struct Overrides {
+    days: Document,
+}
+
+fn section(day: u8) -> String {
+    format!("day{day:02}")
+}
+
+impl Overrides {
+    /// Reads the config file, if any, and applies the changes from the
+    /// command line on top of it, which are for the first of `days`.
+    pub fn load(args: &ParamArgs, days: &RangeInclusive<u8>) -> Result<Overrides, String> {
+        let mut overrides = Overrides::default();
+
+        if let Some(path) = &args.config {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+            let doc = toml::parse(&text)
+                .map_err(|e| format!("{}: {}", path.display(), e.locate(&text)))?;
+            for (name, table) in doc {
+                let known = (1..=25).any(|d| section(d) == name);
+                if name.is_empty() && !table.is_empty() {
+                    return Err(format!(
+                        "{}: parameters must be in a `[dayNN]` section",
+                        path.display()
+                    ));
+                }
+                if !name.is_empty() && !known {
+                    return Err(format!(
+                        "{}: unknown section `[{name}]`, expected `[dayNN]`",
+                        path.display()
+                    ));
+                }
+                overrides.days.insert(name, table);
+            }
+        }
+
+        let table = overrides.days.entry(section(*days.start())).or_default();
+        table.extend(args.overrides.clone());
+        Ok(overrides)
+    }
+
+    pub fn get(&self, day: u8) -> Table {
+        self.days.get(&section(day)).cloned().unwrap_or_default()
+    }
+}
+
+/// Prints the parameters of the selected days in the config file format.
+pub fn params(args: &ParamsArgs) -> ExitCode {
+    let overrides = match Overrides::load(&args.params, &args.days) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut first = true;
+    for day in args.days.clone() {
+        let values = match (days::get(day).params)(&overrides.get(day)) {
+            Ok(values) => values,
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if values.is_empty() {
+            continue;
+        }
+
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("[{}]", section(day));
+        for (name, value) in values {
+            println!("{name} = {value}");
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,62 @@
+//! Drawing what a day's solution does.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+use aoc_common::input;
+use aoc_common::render::Canvas;
+
+use crate::cli::{RenderArgs, RenderFormat};
+use crate::days;
+use crate::params::Overrides;
+
+fn write(canvas: &Canvas, args: &RenderArgs, w: &mut impl Write) -> io::Result<()> {
+    match args.format {
+        RenderFormat::Ascii => w.write_all(canvas.to_ascii().as_bytes())?,
+        RenderFormat::Ansi => w.write_all(canvas.to_ansi().as_bytes())?,
+        RenderFormat::Ppm => canvas.write_ppm(w, args.scale)?,
+    }
+    w.flush()
+}
+
+pub fn render(args: &RenderArgs) -> ExitCode {
+    let day = args.day;
+    let entry = days::get(day);
+    let overrides = match Overrides::load(&args.params, &(day..=day)) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let canvas = input::read(day, args.input.as_ref(), &[])
+        .map_err(|e| format!("day {day}: {e}"))
+        .and_then(|s| (entry.render)(&s, &overrides.get(day)));
+    let canvas = match canvas {
+        Ok(Some(canvas)) => canvas,
+        Ok(None) => {
+            eprintln!("error: day {day} has nothing to render");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let res = match &args.output {
+        Some(path) => File::create(path)
+            .and_then(|f| write(&canvas, args, &mut BufWriter::new(f)))
+            .map_err(|e| format!("cannot write {}: {e}", path.display())),
+        None => write(&canvas, args, &mut io::stdout().lock()).map_err(|e| e.to_string()),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
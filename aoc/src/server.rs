@@ -0,0 +1,203 @@
+//! A stand-in for the puzzle server, serving local inputs and judging
+//! answers against the manifest, to try the client without the real one.
+//!
+//! It answers in the same words as the real server, which is what the
+//! client goes by.
+
+use std::collections::{BTreeMap, HashSet};
+use std::net::TcpListener;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_common::input;
+
+use crate::cli::ServeArgs;
+use crate::http::{self, Request, Response};
+use crate::verify::Manifest;
+
+pub struct Server {
+    pub year: u16,
+    /// Session every request must carry, any if `None`.
+    pub session: Option<String>,
+    /// How long a client has to wait after a wrong answer.
+    pub penalty: Duration,
+    inputs: BTreeMap<u8, String>,
+    answers: Manifest,
+    solved: HashSet<(u8, u8)>,
+    wait_until: Option<Instant>,
+}
+
+fn respond(status: u16, body: impl Into<String>) -> Response {
+    Response {
+        status,
+        body: body.into(),
+    }
+}
+
+/// `d` the way the server words a wait, like `1m 5s`.
+fn wait_text(d: Duration) -> String {
+    let secs = d.as_secs_f64().ceil() as u64;
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+impl Server {
+    pub fn new(inputs: BTreeMap<u8, String>, answers: Manifest) -> Server {
+        Server {
+            year: crate::client::YEAR,
+            session: None,
+            penalty: Duration::from_secs(60),
+            inputs,
+            answers,
+            solved: HashSet::new(),
+            wait_until: None,
+        }
+    }
+
+    /// The day of a path like `/2023/day/1/input`, with what's after it.
+    fn route<'a>(&self, path: &'a str) -> Option<(u8, &'a str)> {
+        let rest = path.strip_prefix(&format!("/{}/day/", self.year))?;
+        let (day, what) = rest.split_once('/')?;
+        let day = day.parse().ok().filter(|d| (1..=25).contains(d))?;
+        Some((day, what))
+    }
+
+    pub fn handle(&mut self, req: &Request) -> Response {
+        let session = req.cookie("session");
+        let logged_in = match &self.session {
+            Some(expected) => session == Some(expected.as_str()),
+            None => session.is_some_and(|s| !s.is_empty()),
+        };
+
+        match (req.method.as_str(), self.route(&req.path)) {
+            (_, None) => respond(404, "404 Not Found"),
+            _ if !logged_in => respond(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            ("GET", Some((day, "input"))) => match self.inputs.get(&day) {
+                Some(input) => respond(200, input.clone()),
+                None => respond(404, "404 Not Found"),
+            },
+            ("POST", Some((day, "answer"))) => self.answer(day, &req.body),
+            _ => respond(405, "405 Method Not Allowed"),
+        }
+    }
+
+    fn answer(&mut self, day: u8, body: &str) -> Response {
+        let form = http::form_decode(body);
+        let field = |name| form.iter().find(|(n, _)| n == name).map(|(_, v)| v.trim());
+        let (Some(level), Some(answer)) = (field("level"), field("answer")) else {
+            return respond(400, "400 Bad Request");
+        };
+
+        if let Some(until) = self.wait_until {
+            let now = Instant::now();
+            if now < until {
+                return respond(
+                    200,
+                    format!(
+                        "You gave an answer too recently; you have to wait after submitting an \
+                         answer before trying again.  You have {} left to wait.",
+                        wait_text(until - now)
+                    ),
+                );
+            }
+        }
+
+        let part = level.parse().unwrap_or(0);
+        let open = match part {
+            1 => true,
+            2 => self.solved.contains(&(day, 1)),
+            _ => false,
+        };
+        let expected =
+            match self.answers.get(day, part) {
+                Some(expected) if open && !self.solved.contains(&(day, part)) => expected,
+                _ => return respond(
+                    200,
+                    "You don't seem to be solving the right level.  Did you already complete it?",
+                ),
+            };
+
+        if answer == expected {
+            self.solved.insert((day, part));
+            return respond(
+                200,
+                "That's the right answer!  You are one gold star closer.",
+            );
+        }
+
+        self.wait_until = Some(Instant::now() + self.penalty);
+        let hint = match (answer.parse::<i128>(), expected.parse::<i128>()) {
+            (Ok(a), Ok(e)) if a > e => "; your answer is too high",
+            (Ok(a), Ok(e)) if a < e => "; your answer is too low",
+            _ => "",
+        };
+        respond(
+            200,
+            format!(
+                "That's not the right answer{hint}.  Please wait {} seconds before trying again.",
+                self.penalty.as_secs()
+            ),
+        )
+    }
+
+    /// Answers requests one at a time until the listener fails.
+    pub fn serve(&mut self, listener: TcpListener) -> std::io::Result<()> {
+        for stream in listener.incoming() {
+            let mut stream = stream?;
+            let res = http::read_request(&mut stream).and_then(|req| {
+                let response = self.handle(&req);
+                http::write_response(&mut stream, &response)
+            });
+            if let Err(e) = res {
+                eprintln!("warning: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serves the inputs found in the usual places and the answers of the
+/// manifest on localhost.
+pub fn serve(args: &ServeArgs) -> ExitCode {
+    let answers = match Manifest::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let inputs: BTreeMap<_, _> = (1..=25)
+        .filter_map(|day| Some((day, input::read(day, None, &[]).ok()?)))
+        .collect();
+
+    let listener = match TcpListener::bind(("127.0.0.1", args.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: cannot listen on port {}: {e}", args.port);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut server = Server::new(inputs, answers);
+    server.year = args.year;
+    server.session = args.session.clone();
+    server.penalty = Duration::from_secs(args.penalty);
+
+    eprintln!(
+        "serving {} inputs for {} on http://127.0.0.1:{}",
+        server.inputs.len(),
+        server.year,
+        args.port
+    );
+    match server.serve(listener) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
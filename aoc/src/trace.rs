@@ -0,0 +1,203 @@
+//! Following what a day's simulation does through the events it emits.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+use std::rc::Rc;
+
+use aoc_common::input;
+use aoc_common::trace::{self, Event, Filter};
+
+use crate::cli::{Format, TraceArgs};
+use crate::days;
+use crate::json;
+use crate::params::Overrides;
+
+/// Writes the events of one part that pass the filter, numbered by their
+/// place among all of them so the steps don't change with the filter.
+struct Printer {
+    out: Box<dyn Write>,
+    format: Format,
+    filter: Filter,
+    limit: Option<usize>,
+    part: u8,
+    steps: usize,
+    shown: usize,
+    error: Option<io::Error>,
+}
+
+impl Printer {
+    fn start(&mut self, part: u8) {
+        self.part = part;
+        self.steps = 0;
+        self.shown = 0;
+    }
+
+    fn event(&mut self, event: &Event) {
+        self.steps += 1;
+        if self.error.is_some()
+            || self.limit.is_some_and(|limit| self.shown >= limit)
+            || !self.filter.matches(event)
+        {
+            return;
+        }
+        self.shown += 1;
+        if let Err(e) = self.write(event) {
+            self.error = Some(e);
+        }
+    }
+
+    fn write(&mut self, event: &Event) -> io::Result<()> {
+        let (part, step, kind) = (self.part, self.steps, event.kind);
+        match self.format {
+            Format::Text => {
+                write!(self.out, "p{part} #{step} {kind}")?;
+                for (name, value) in &event.fields {
+                    write!(self.out, " {name}={value}")?;
+                }
+                writeln!(self.out)
+            }
+            Format::Json => {
+                let fields: Vec<_> = event
+                    .fields
+                    .iter()
+                    .map(|(name, value)| format!("{}:{}", json::string(name), json::string(value)))
+                    .collect();
+                writeln!(
+                    self.out,
+                    "{{\"part\":{part},\"step\":{step},\"kind\":{},\"fields\":{{{}}}}}",
+                    json::string(kind),
+                    fields.join(",")
+                )
+            }
+        }
+    }
+}
+
+/// Checks the filter only asks for kinds of events the day emits.
+fn check_kinds(day: u8, events: &[&str], filter: &Filter) -> Result<(), String> {
+    if events.is_empty() {
+        return Err(format!("day {day} has nothing to trace"));
+    }
+    match filter.kinds.iter().find(|k| !events.contains(&k.as_str())) {
+        Some(kind) => {
+            let kinds: Vec<_> = events.iter().map(|k| format!("`{k}`")).collect();
+            Err(format!(
+                "day {day} has no `{kind}` events, expected one of {}",
+                kinds.join(", ")
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Solves the parts one after the other, printing their events as they
+/// come and each answer on stderr.
+pub fn trace(args: &TraceArgs) -> ExitCode {
+    let day = args.day;
+    let entry = days::get(day);
+    if let Err(e) = check_kinds(day, entry.events, &args.filter) {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+    let Some(parts) = entry.select(args.part) else {
+        eprintln!("error: day {day} has no part {}", args.part.unwrap());
+        return ExitCode::FAILURE;
+    };
+    let overrides = match Overrides::load(&args.params, &(day..=day)) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let instr = match input::read(day, args.input.as_ref(), &[]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: day {day}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let out: Box<dyn Write> = match &args.output {
+        Some(path) => match File::create(path) {
+            Ok(f) => Box::new(BufWriter::new(f)),
+            Err(e) => {
+                eprintln!("error: cannot write {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    let printer = Rc::new(RefCell::new(Printer {
+        out,
+        format: args.format,
+        filter: args.filter.clone(),
+        limit: args.limit,
+        part: 0,
+        steps: 0,
+        shown: 0,
+        error: None,
+    }));
+
+    for part in parts {
+        printer.borrow_mut().start(part);
+        let sink = Rc::clone(&printer);
+        let solved = trace::record(
+            move |event| sink.borrow_mut().event(event),
+            || (entry.solve)(&instr, &[part], &overrides.get(day)),
+        );
+
+        let mut printer = printer.borrow_mut();
+        if let Err(e) = printer.out.flush() {
+            printer.error.get_or_insert(e);
+        }
+        match solved {
+            Ok(solved) => eprintln!(
+                "Part {part}: {} ({} of {} events shown)",
+                solved.answers[0], printer.shown, printer.steps
+            ),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let error = printer.borrow_mut().error.take();
+    match error {
+        // like the reader of a pipe going away after the first lines
+        Some(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        None => ExitCode::SUCCESS,
+        Some(e) => {
+            eprintln!("error: cannot write the events: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_kinds() {
+        let filter = |kinds: &[&str]| Filter {
+            kinds: kinds.iter().map(|k| k.to_string()).collect(),
+            fields: vec![],
+        };
+        let events = days::get(20).events;
+
+        assert!(check_kinds(20, events, &filter(&[])).is_ok());
+        assert!(check_kinds(20, events, &filter(&["pulse", "press"])).is_ok());
+        assert_eq!(
+            check_kinds(20, events, &filter(&["split"])).unwrap_err(),
+            "day 20 has no `split` events, expected one of `press`, `pulse`, `low`"
+        );
+        assert_eq!(
+            check_kinds(1, days::get(1).events, &filter(&[])).unwrap_err(),
+            "day 1 has nothing to trace"
+        );
+    }
+}
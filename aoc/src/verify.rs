@@ -0,0 +1,195 @@
+//! Checking the answers against a manifest of known good ones.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_common::input;
+use aoc_common::parallel;
+use aoc_common::toml::{self, Table};
+
+use crate::cli::VerifyArgs;
+use crate::days;
+
+/// Expected answers by day and part, read from sections like
+///
+/// ```toml
+/// [day01]
+/// part1 = 54632
+/// part2 = 54019
+/// ```
+#[derive(Debug)]
+pub struct Manifest {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Manifest {
+    pub fn parse(s: &str) -> Result<Manifest, String> {
+        let doc = toml::parse(s).map_err(|e| e.locate(s).to_string())?;
+
+        let mut answers = BTreeMap::new();
+        for (section, table) in doc {
+            if section.is_empty() {
+                if let Some(key) = table.keys().next() {
+                    return Err(format!("`{key}` is outside of a `[dayNN]` section"));
+                }
+                continue;
+            }
+            let day = section
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .filter(|d| (1..=25).contains(d))
+                .ok_or(format!("unknown section `[{section}]`"))?;
+            let parts = days::get(day).parts;
+            for (key, value) in table {
+                let part = key
+                    .strip_prefix("part")
+                    .and_then(|p| p.parse().ok())
+                    .filter(|p| (1..=parts).contains(p))
+                    .ok_or(format!("unknown key `{key}` in `[{section}]`"))?;
+                answers.insert((day, part), value.to_string());
+            }
+        }
+        Ok(Manifest { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Manifest, String> {
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Manifest::parse(&s).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+struct Mismatch {
+    day: u8,
+    part: u8,
+    expected: String,
+    actual: String,
+}
+
+fn print_table(rows: &[Mismatch]) {
+    let expected_w = rows.iter().map(|r| r.expected.len()).max().unwrap_or(0);
+    let expected_w = expected_w.max("expected".len());
+
+    println!("day  part  {:expected_w$}  actual", "expected");
+    for r in rows {
+        println!(
+            "{:<3}  {:<4}  {:expected_w$}  {}",
+            r.day, r.part, r.expected, r.actual
+        );
+    }
+}
+
+pub fn verify(args: &VerifyArgs) -> ExitCode {
+    let manifest = match Manifest::load(&args.answers) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut checked = 0;
+    let mut unknown = vec![];
+    let mut mismatches = vec![];
+
+    let mut checks = vec![];
+    for day in args.days.clone() {
+        let mut parts = vec![];
+        let mut expected = vec![];
+        for part in 1..=days::get(day).parts {
+            match manifest.get(day, part) {
+                Some(answer) => {
+                    parts.push(part);
+                    expected.push(answer);
+                }
+                None => unknown.push((day, part)),
+            }
+        }
+        if !parts.is_empty() {
+            checked += parts.len();
+            checks.push((day, parts, expected));
+        }
+    }
+
+    parallel::set_threads(args.jobs);
+    let results = parallel::stream(&checks, |(day, parts, _)| {
+        input::read(*day, None, &[])
+            .map_err(|e| e.to_string())
+            .and_then(|s| (days::get(*day).solve)(&s, parts, &Table::new()))
+            .map(|solved| solved.answers)
+    });
+
+    for ((day, parts, expected), actual) in checks.iter().zip(results) {
+        let mut ok = true;
+        for (i, (&part, expected)) in parts.iter().zip(expected).enumerate() {
+            let actual = match &actual {
+                Ok(answers) => answers[i].to_string(),
+                Err(e) => format!("error: {e}"),
+            };
+            if actual != *expected {
+                ok = false;
+                mismatches.push(Mismatch {
+                    day: *day,
+                    part,
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+        println!("Day {day}: {}", if ok { "ok" } else { "MISMATCH" });
+    }
+
+    for (day, part) in &unknown {
+        println!("Day {day}: no expected answer for part {part}, skipped");
+    }
+
+    if mismatches.is_empty() {
+        println!("\nall {checked} answers match");
+        ExitCode::SUCCESS
+    } else {
+        println!("\n{} of {checked} answers differ:\n", mismatches.len());
+        print_table(&mismatches);
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest() {
+        let manifest =
+            Manifest::parse("[day01]\npart1 = 142\npart2 = \"abc\"\n\n[day25]\npart1 = 54\n")
+                .unwrap();
+        assert_eq!(manifest.get(1, 1), Some("142"));
+        assert_eq!(manifest.get(1, 2), Some("abc"));
+        assert_eq!(manifest.get(25, 1), Some("54"));
+        assert_eq!(manifest.get(2, 1), None);
+    }
+
+    #[test]
+    fn bad_manifests() {
+        let err = |s| Manifest::parse(s).unwrap_err();
+        assert!(err("[day01]\npart1 142\n").starts_with("line 2, "));
+        assert_eq!(
+            err("part1 = 1\n[day01]\n"),
+            "`part1` is outside of a `[dayNN]` section"
+        );
+        assert_eq!(err("[day26]\npart1 = 1\n"), "unknown section `[day26]`");
+        assert_eq!(err("[answers]\n"), "unknown section `[answers]`");
+        assert_eq!(
+            err("[day01]\npart3 = 1\n"),
+            "unknown key `part3` in `[day01]`"
+        );
+        assert_eq!(
+            err("[day25]\npart2 = 1\n"),
+            "unknown key `part2` in `[day25]`"
+        );
+    }
+}
@@ -0,0 +1,498 @@
+//! Small parser combinators for the puzzle input formats.
+//!
+//! A parser reads from the start of a string and returns what it read with
+//! the rest of the string. Failures are [`ParseError`]s pointing at the
+//! slice of the input where the parser gave up, so
+//! [`ParseError::locate`] can tell their line and column.
+//!
+//! Lines are parsed one at a time with [`lines`], which must read each of
+//! them to the end, and blocks of lines are separated by blank lines with
+//! [`sections`]. [`parse_all`] runs a parser on a whole input.
+
+use std::str::FromStr;
+
+use crate::error::{ParseError, ParseResult};
+use crate::grid::{self, Grid};
+use crate::parse::{char_at, end};
+
+/// A value read from the start of a string, with the rest of the string.
+pub type Parsed<'a, T> = ParseResult<(T, &'a str)>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, s: &'a str) -> Parsed<'a, T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, U> {
+            let (v, rest) = self.parse(s)?;
+            Ok((f(v), rest))
+        }
+    }
+
+    /// Converts the value with `f`, which fails with what it expected
+    /// instead of the text the value was read from.
+    fn try_map<U>(self, f: impl Fn(T) -> Result<U, String>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, U> {
+            let (v, rest) = self.parse(s)?;
+            let read = &s[..s.len() - rest.len()];
+            match f(v) {
+                Ok(u) => Ok((u, rest)),
+                Err(expected) => Err(ParseError::new(expected, read)),
+            }
+        }
+    }
+
+    /// Converts the value with `f`, which fails with its own error, for
+    /// checks pointing into the text the value was read from.
+    fn and_then<U>(self, f: impl Fn(T) -> ParseResult<U>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, U> {
+            let (v, rest) = self.parse(s)?;
+            Ok((f(v)?, rest))
+        }
+    }
+
+    /// The text `self` read instead of its value.
+    fn recognize(self) -> impl Parser<'a, &'a str>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, &'a str> {
+            let (_, rest) = self.parse(s)?;
+            Ok((&s[..s.len() - rest.len()], rest))
+        }
+    }
+
+    /// The text `self` read along with its value.
+    fn with_text(self) -> impl Parser<'a, (&'a str, T)>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, (&'a str, T)> {
+            let (v, rest) = self.parse(s)?;
+            Ok(((&s[..s.len() - rest.len()], v), rest))
+        }
+    }
+
+    /// `self` followed by `next`, with both values.
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, (T, U)> {
+            let (a, rest) = self.parse(s)?;
+            let (b, rest) = next.parse(rest)?;
+            Ok(((a, b), rest))
+        }
+    }
+
+    /// `self` followed by `next`, keeping the value of `self`.
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        self.then(next).map(|(a, _)| a)
+    }
+
+    /// `self` or, if it fails, `other` from the same place. The error is
+    /// the one of `other`.
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, T> { self.parse(s).or_else(|_| other.parse(s)) }
+    }
+
+    /// `self` if it matches, else nothing is read.
+    fn opt(self) -> impl Parser<'a, Option<T>>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, Option<T>> {
+            match self.parse(s) {
+                Ok((v, rest)) => Ok((Some(v), rest)),
+                Err(_) => Ok((None, s)),
+            }
+        }
+    }
+
+    /// As many `self` as match in a row, at least one.
+    fn many1(self) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, Vec<T>> {
+            let (first, mut rest) = self.parse(s)?;
+            let mut items = vec![first];
+            while let Ok((item, after)) = self.parse(rest) {
+                items.push(item);
+                rest = after;
+            }
+            Ok((items, rest))
+        }
+    }
+
+    /// At least one `self`, separated by `sep`. Once a separator is read,
+    /// another item must follow.
+    fn sep_by<U>(self, sep: impl Parser<'a, U>) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |s: &'a str| -> Parsed<'a, Vec<T>> {
+            let (first, mut rest) = self.parse(s)?;
+            let mut items = vec![first];
+            while let Ok((_, after)) = sep.parse(rest) {
+                let (item, after) = self.parse(after)?;
+                items.push(item);
+                rest = after;
+            }
+            Ok((items, rest))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> Parsed<'a, T>,
+{
+    fn parse(&self, s: &'a str) -> Parsed<'a, T> {
+        self(s)
+    }
+}
+
+/// The next character of `s` for an error, or the end of `s`.
+fn next_char(s: &str) -> &str {
+    char_at(s, 0)
+}
+
+/// The text `t`.
+pub fn tag<'a>(t: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| -> Parsed<'a, &'a str> {
+        match s.strip_prefix(t) {
+            Some(rest) => Ok((&s[..t.len()], rest)),
+            None => Err(ParseError::new(
+                format!("`{}`", t.escape_debug()),
+                next_char(s),
+            )),
+        }
+    }
+}
+
+/// One or more characters matching `f`, `expected` describes them.
+pub fn take_while1<'a>(
+    expected: &'static str,
+    f: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| -> Parsed<'a, &'a str> {
+        let len = s.find(|c| !f(c)).unwrap_or(s.len());
+        if len == 0 {
+            return Err(ParseError::new(expected, next_char(s)));
+        }
+        Ok((&s[..len], &s[len..]))
+    }
+}
+
+/// One or more spaces.
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a space", |c| c == ' ')
+}
+
+/// A name of letters and digits.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a name", |c| c.is_ascii_alphanumeric())
+}
+
+/// One character of `chars`.
+pub fn char_in<'a>(chars: &'static str) -> impl Parser<'a, char> {
+    move |s: &'a str| -> Parsed<'a, char> {
+        match s.chars().next() {
+            Some(c) if chars.contains(c) => Ok((c, &s[c.len_utf8()..])),
+            _ => Err(ParseError::new(grid::one_of(chars), next_char(s))),
+        }
+    }
+}
+
+/// An unsigned decimal number.
+pub fn uint<'a, T: FromStr>() -> impl Parser<'a, T> {
+    take_while1("a number", |c| c.is_ascii_digit())
+        .try_map(|digits| digits.parse().map_err(|_| "a smaller number".to_string()))
+}
+
+/// A decimal number, negative with a leading `-`.
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |s: &'a str| -> Parsed<'a, T> {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        let (_, rest) = take_while1("a number", |c| c.is_ascii_digit()).parse(digits)?;
+        let read = &s[..s.len() - rest.len()];
+        match read.parse() {
+            Ok(n) => Ok((n, rest)),
+            Err(_) => Err(ParseError::new("a smaller number", read)),
+        }
+    }
+}
+
+/// A number of exactly `len` hexadecimal digits.
+pub fn hex<'a>(len: usize) -> impl Parser<'a, u64> {
+    move |s: &'a str| -> Parsed<'a, u64> {
+        let expected = || format!("{len} hexadecimal digits");
+        match s
+            .char_indices()
+            .take(len)
+            .find(|(_, c)| !c.is_ascii_hexdigit())
+        {
+            Some((i, _)) => Err(ParseError::new(expected(), char_at(s, i))),
+            None if s.len() < len => Err(ParseError::new(expected(), end(s))),
+            None => Ok((u64::from_str_radix(&s[..len], 16).unwrap(), &s[len..])),
+        }
+    }
+}
+
+/// `p` after `before`.
+pub fn preceded<'a, A, T>(before: impl Parser<'a, A>, p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    before.then(p).map(|(_, v)| v)
+}
+
+/// `p` between the texts `open` and `close`, like `(...)` or `{...}`.
+pub fn bracketed<'a, T>(
+    open: &'static str,
+    p: impl Parser<'a, T>,
+    close: &'static str,
+) -> impl Parser<'a, T> {
+    preceded(tag(open), p).skip(tag(close))
+}
+
+/// `key` and `value` separated by the text `sep`, like `x=12`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    sep: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    key.skip(tag(sep)).then(value)
+}
+
+/// The rest of the line, possibly empty, without a `\r` ending it.
+pub fn rest_of_line<'a>() -> impl Parser<'a, &'a str> {
+    move |s: &'a str| -> Parsed<'a, &'a str> {
+        let len = s.find('\n').unwrap_or(s.len());
+        let text = s[..len].strip_suffix('\r').unwrap_or(&s[..len]);
+        Ok((text, &s[text.len()..]))
+    }
+}
+
+/// A line break, `\n` or `\r\n`.
+pub fn newline<'a>() -> impl Parser<'a, &'a str> {
+    tag("\r\n").or(tag("\n"))
+}
+
+/// A line break followed by a blank line.
+pub fn blank_line<'a>() -> impl Parser<'a, &'a str> {
+    newline().then(newline()).recognize()
+}
+
+/// `p` reading all of a line, without its line break.
+pub fn line<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |s: &'a str| -> Parsed<'a, T> {
+        let (text, rest) = rest_of_line().parse(s)?;
+        if text.is_empty() {
+            return Err(ParseError::new("a line", text));
+        }
+        let (v, left) = p.parse(text)?;
+        if !left.is_empty() {
+            return Err(ParseError::new("end of line", next_char(left)));
+        }
+        Ok((v, rest))
+    }
+}
+
+/// Consecutive lines each read by `p`, up to a blank line or the end. The
+/// line break after the last one isn't read.
+pub fn lines<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    line(p).sep_by(move |s: &'a str| -> Parsed<'a, ()> {
+        match newline().parse(s) {
+            Ok((_, next)) if !next.is_empty() && newline().parse(next).is_err() => Ok(((), next)),
+            _ => Err(ParseError::new("another line", s)),
+        }
+    })
+}
+
+/// Blocks read by `p`, separated by blank lines.
+pub fn sections<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    p.sep_by(blank_line())
+}
+
+/// A grid of the lines up to a blank line or the end, `f` converts each
+/// character like for [`grid::parse_grid_with`].
+pub fn grid_with<'a, T>(f: impl Fn(char) -> Result<T, String>) -> impl Parser<'a, Grid<T>> {
+    move |s: &'a str| -> Parsed<'a, Grid<T>> {
+        let (_, rest) = lines(rest_of_line()).parse(s)?;
+        let g = grid::parse_grid_with(&s[..s.len() - rest.len()], &f)?;
+        Ok((g, rest))
+    }
+}
+
+/// A grid of the characters in `tiles`.
+pub fn grid<'a>(tiles: &'static str) -> impl Parser<'a, Grid<char>> {
+    grid_with(move |c| {
+        if tiles.contains(c) {
+            Ok(c)
+        } else {
+            Err(grid::one_of(tiles))
+        }
+    })
+}
+
+/// Reads all of `s` with `p`, only whitespace may be left.
+pub fn parse_all<'a, T>(p: impl Parser<'a, T>, s: &'a str) -> ParseResult<T> {
+    let (v, rest) = p.parse(s)?;
+    let left = rest.trim_start();
+    if !left.is_empty() {
+        return Err(ParseError::new("end of input", next_char(left)));
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Where `p` fails on `s`, as `line:column: message`.
+    fn error<'a, T>(p: impl Parser<'a, T>, s: &'a str) -> String {
+        let e = parse_all(p, s).err().expect("should fail").locate(s);
+        let msg = e.to_string();
+        let msg = msg.split(", ").skip(2).collect::<Vec<_>>().join(", ");
+        format!("{}:{}: {msg}", e.line.unwrap(), e.column.unwrap())
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_all(uint::<u32>(), "123"), Ok(123));
+        assert_eq!(parse_all(int::<i64>(), "-45"), Ok(-45));
+        assert_eq!(parse_all(hex(5).then(hex(1)), "70c710"), Ok((0x70c71, 0)));
+        assert_eq!(
+            error(uint::<u8>(), "300"),
+            "1:1: expected a smaller number, found `300`"
+        );
+        assert_eq!(
+            error(int::<i32>(), "-x"),
+            "1:2: expected a number, found `x`"
+        );
+        assert_eq!(
+            error(hex(6), "12g456"),
+            "1:3: expected 6 hexadecimal digits, found `g`"
+        );
+    }
+
+    #[test]
+    fn combined() {
+        let pos = || int::<i64>().sep_by(tag(",").then(spaces().opt()));
+        let hail = || pos().skip(tag(" @ ")).then(pos());
+        assert_eq!(
+            parse_all(hail(), "19, 13, 30 @ -2,  1, -2"),
+            Ok((vec![19, 13, 30], vec![-2, 1, -2]))
+        );
+        assert_eq!(
+            error(hail(), "19, 13, 30 @ -2,  1,"),
+            "1:21: expected a number, found end of input"
+        );
+
+        let step = || word().then(tag("=").then(uint::<u32>())).with_text();
+        assert_eq!(
+            parse_all(step().sep_by(tag(",")), "rn=1,cm=20"),
+            Ok(vec![
+                ("rn=1", ("rn", ("=", 1))),
+                ("cm=20", ("cm", ("=", 20)))
+            ])
+        );
+
+        let part = || {
+            bracketed(
+                "{",
+                key_value(word(), "=", uint::<u32>()).sep_by(tag(",")),
+                "}",
+            )
+        };
+        assert_eq!(
+            parse_all(part(), "{x=787,m=2655}"),
+            Ok(vec![("x", 787), ("m", 2655)])
+        );
+        assert_eq!(
+            error(part(), "{x=787;m=2655}"),
+            "1:7: expected `}`, found `;`"
+        );
+
+        let step = || {
+            word().then(
+                tag("-")
+                    .map(|_| None)
+                    .or(preceded(tag("="), uint::<u8>()).map(Some)),
+            )
+        };
+        assert_eq!(parse_all(step(), "rn=1"), Ok(("rn", Some(1))));
+        assert_eq!(parse_all(step(), "cm-"), Ok(("cm", None)));
+        assert_eq!(error(step(), "cm+"), "1:3: expected `=`, found `+`");
+        let card = || preceded(spaces(), uint::<u8>()).many1();
+        assert_eq!(
+            parse_all(card().skip(tag(" |")).then(card()), " 41 48 |  6 31"),
+            Ok((vec![41, 48], vec![6, 31]))
+        );
+        assert_eq!(
+            error(char_in("LR"), "X"),
+            "1:1: expected one of `L`, `R`, found `X`"
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let input = "seeds: 1 2\n\n1 2\n3 4\n\n#.\n.#\n";
+        let numbers = || uint::<u32>().sep_by(spaces());
+        let p = line(preceded(tag("seeds: "), numbers()))
+            .skip(tag("\n\n"))
+            .then(lines(numbers()))
+            .skip(tag("\n\n"))
+            .then(grid(".#"));
+        let ((seeds, rows), g) = parse_all(p, input).unwrap();
+        assert_eq!(seeds, [1, 2]);
+        assert_eq!(rows, [vec![1, 2], vec![3, 4]]);
+        assert_eq!(g.width(), 2);
+
+        assert_eq!(
+            parse_all(sections(lines(numbers())), "1 2\n3\n\n4\n").unwrap(),
+            [vec![vec![1, 2], vec![3]], vec![vec![4]]]
+        );
+        assert_eq!(
+            error(lines(numbers()), "1 2\n3 x\n"),
+            "2:3: expected a number, found `x`"
+        );
+        assert_eq!(
+            error(grid(".#"), "#.\n.x\n"),
+            "2:2: expected one of `.`, `#`, found `x`"
+        );
+        assert_eq!(
+            error(lines(numbers()), "1\n\n2\n"),
+            "3:1: expected end of input, found `2`"
+        );
+    }
+
+    #[test]
+    fn crlf() {
+        let numbers = || lines(uint::<u32>().sep_by(spaces()));
+        assert_eq!(
+            parse_all(sections(numbers()), "1 2\r\n3\r\n\r\n4\r\n").unwrap(),
+            [vec![vec![1, 2], vec![3]], vec![vec![4]]]
+        );
+        let p = line(word()).skip(blank_line()).then(grid(".#"));
+        let (name, g) = parse_all(p, "ab\r\n\r\n#.\r\n.#\r\n").unwrap();
+        assert_eq!((name, g.width(), g.height()), ("ab", 2, 2));
+        assert_eq!(
+            error(numbers(), "1 2\r\n3 x\r\n"),
+            "2:3: expected a number, found `x`"
+        );
+    }
+}
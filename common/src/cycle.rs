@@ -0,0 +1,136 @@
+//! Cycle detection in sequences `x, f(x), f(f(x)), ...` of a deterministic
+//! step function over finitely many states, to jump to far away steps.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of an eventually periodic sequence: `tail` states that are never
+/// seen again, then `period` states repeating forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub tail: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Index of the first state equal to the one at index `n`, which is less
+    /// than `tail + period`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.tail {
+            n
+        } else {
+            self.tail + (n - self.tail) % self.period
+        }
+    }
+}
+
+/// Finds the cycle by remembering every state, returning it with the states
+/// up to the first repeat, so any state is `states[cycle.index(n)]`.
+pub fn hashed<S: Clone + Eq + Hash>(init: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut cur = init;
+    loop {
+        if let Some(&tail) = seen.get(&cur) {
+            let period = states.len() - tail;
+            return (Cycle { tail, period }, states);
+        }
+        seen.insert(cur.clone(), states.len());
+        let next = step(&cur);
+        states.push(cur);
+        cur = next;
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, holding only two states at a time
+/// at the cost of stepping through the sequence about three times.
+pub fn brent<S: Clone + Eq>(init: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // the hare moves ahead until it meets the tortoise, which jumps to the
+    // hare at every power of two, giving the period
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = init.clone();
+    let mut hare = step(&init);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // with the hare a period ahead, they first meet where the cycle starts
+    let mut tortoise = init.clone();
+    let mut hare = init;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        tail += 1;
+    }
+
+    Cycle { tail, period }
+}
+
+/// State at index `n` of the sequence, using [`hashed`].
+pub fn nth<S: Clone + Eq + Hash>(init: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (cycle, mut states) = hashed(init, step);
+    states.swap_remove(cycle.index(n))
+}
+
+/// State at index `n` of the sequence, using [`brent`] when the states are
+/// too big or too many to keep.
+pub fn nth_brent<S: Clone + Eq>(init: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = brent(init.clone(), &mut step);
+    let mut cur = init;
+    for _ in 0..cycle.index(n) {
+        cur = step(&cur);
+    }
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn both_agree() {
+        for init in 0..255 {
+            let (cycle, states) = hashed(init, step);
+            assert_eq!(brent(init, step), cycle);
+            assert_eq!(states.len(), cycle.tail + cycle.period);
+        }
+
+        // 3 -> 10 -> 101 -> 2 -> 5 -> 26 -> 167 -> 95 -> 101
+        let (cycle, _) = hashed(3, step);
+        assert_eq!(cycle, Cycle { tail: 2, period: 6 });
+    }
+
+    #[test]
+    fn far_states() {
+        let mut x = 3;
+        for n in 0..50 {
+            assert_eq!(nth(3, step, n), x);
+            assert_eq!(nth_brent(3, step, n), x);
+            x = step(&x);
+        }
+        assert_eq!(
+            nth(3, step, 1_000_000_000),
+            nth(3, step, 2 + 999_999_998 % 6)
+        );
+    }
+
+    #[test]
+    fn fixed_point() {
+        assert_eq!(hashed(7, |x| *x).0, Cycle { tail: 0, period: 1 });
+        assert_eq!(brent(7, |x| *x), Cycle { tail: 0, period: 1 });
+    }
+}
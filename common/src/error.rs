@@ -0,0 +1,84 @@
+//! Errors reported for malformed puzzle inputs.
+
+use std::error::Error;
+use std::fmt;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A puzzle input that doesn't match the expected format.
+///
+/// Parsers create it from the offending slice of the input, the line and
+/// column are worked out by [`ParseError::locate`] once the whole input is
+/// known, so nested parsers don't need to thread positions around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub expected: String,
+    /// Offending text, empty when the input ended too early.
+    pub found: String,
+    addr: usize,
+    eof: bool,
+}
+
+impl ParseError {
+    /// `found` should be a slice of the puzzle input, use an empty slice at
+    /// the end of a string to report that something is missing.
+    pub fn new(expected: impl Into<String>, found: &str) -> ParseError {
+        ParseError {
+            day: None,
+            line: None,
+            column: None,
+            expected: expected.into(),
+            found: found.to_string(),
+            addr: found.as_ptr() as usize,
+            eof: false,
+        }
+    }
+
+    pub fn with_day(mut self, day: u8) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+
+    /// Fills in the line and column if the error points into `input`.
+    pub fn locate(mut self, input: &str) -> ParseError {
+        let start = input.as_ptr() as usize;
+        if self.line.is_some() || !(start..=start + input.len()).contains(&self.addr) {
+            return self;
+        }
+
+        let offset = self.addr - start;
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.line = Some(before.matches('\n').count() + 1);
+        self.column = Some(before[line_start..].chars().count() + 1);
+        self.eof = self.found.is_empty() && input[offset..].trim().is_empty();
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {line}, ")?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "column {column}, ")?;
+        }
+        write!(f, "expected {}, found ", self.expected)?;
+        if self.eof {
+            write!(f, "end of input")
+        } else if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "`{}`", self.found.escape_debug())
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,95 @@
+//! Shapes shared by the days' input generators.
+
+use std::collections::HashSet;
+
+use crate::geom::{neighbours4, Dir, Pos};
+use crate::rng::Rng;
+
+/// Cells of a random tree of up to `n * n` nodes, with a node every other
+/// cell and the edges between them, so in `0..2 * n - 1` both ways.
+pub fn tree(rng: &mut Rng, n: i64) -> HashSet<Pos> {
+    let mut nodes = vec![(rng.range(0..n), rng.range(0..n))];
+    let mut seen: HashSet<Pos> = nodes.iter().copied().collect();
+    let mut cells: HashSet<Pos> = nodes.iter().map(|&(x, y)| (2 * x, 2 * y)).collect();
+    for _ in 0..rng.range(0..n * n) {
+        let frontier: Vec<_> = nodes
+            .iter()
+            .flat_map(|&p| neighbours4(p).map(move |q| (p, q)))
+            .filter(|(_, q)| (0..n).contains(&q.0) && (0..n).contains(&q.1) && !seen.contains(q))
+            .collect();
+        if frontier.is_empty() {
+            break;
+        }
+        let (p, q) = *rng.pick(&frontier);
+        nodes.push(q);
+        seen.insert(q);
+        cells.insert((p.0 + q.0, p.1 + q.1));
+        cells.insert((2 * q.0, 2 * q.1));
+    }
+    cells
+}
+
+/// Outline of a random [`tree`]: a closed loop through the corners of the
+/// cells, in order, never touching itself. Corner `(x, y)` is the top left
+/// one of cell `(x, y)`, so they're in `0..2 * n` both ways.
+pub fn outline(rng: &mut Rng, n: i64) -> Vec<Pos> {
+    let cells = tree(rng, n);
+
+    // a side between two corners is on the outline if only one of the cells
+    // along it is in the tree, and a tree has two such sides at each corner
+    let on_outline = |c: Pos, d: Dir| {
+        let (a, b) = match d {
+            Dir::N => ((c.0 - 1, c.1 - 1), (c.0, c.1 - 1)),
+            Dir::S => ((c.0 - 1, c.1), c),
+            Dir::W => ((c.0 - 1, c.1 - 1), (c.0 - 1, c.1)),
+            Dir::E => ((c.0, c.1 - 1), c),
+        };
+        cells.contains(&a) != cells.contains(&b)
+    };
+
+    // the top left corner of the top left node is always on it, going east
+    let start = *cells.iter().min_by_key(|&&(x, y)| (y, x)).unwrap();
+    let mut corners = vec![start];
+    let (mut cur, mut dir) = (Dir::E.step(start), Dir::E);
+    while cur != start {
+        corners.push(cur);
+        dir = Dir::ALL
+            .into_iter()
+            .find(|&d| d != dir.opposite() && on_outline(cur, d))
+            .unwrap();
+        cur = dir.step(cur);
+    }
+    corners
+}
+
+/// A name of `len` lowercase letters not in `used`, which it's added to.
+pub fn name(rng: &mut Rng, used: &mut HashSet<String>, len: usize) -> String {
+    loop {
+        let name: String = (0..len)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::manhattan;
+
+    #[test]
+    fn simple_loops() {
+        for seed in 0..50 {
+            let corners = outline(&mut Rng::new(seed), 6);
+            let distinct: HashSet<_> = corners.iter().collect();
+            assert_eq!(distinct.len(), corners.len());
+            let next = corners.iter().cycle().skip(1);
+            assert!(corners
+                .iter()
+                .zip(next)
+                .all(|(&a, &b)| manhattan(a, b) == 1));
+        }
+    }
+}
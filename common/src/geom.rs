@@ -0,0 +1,237 @@
+//! Positions and directions on a 2D plane where `y` grows downwards, and
+//! points in 3D space, as plain tuples of any signed integer width.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Integer type usable as a coordinate.
+pub trait Coord:
+    Copy
+    + Ord
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + From<i8>
+{
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(impl Coord for $t {
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        })*
+    };
+}
+
+impl_coord!(i16, i32, i64);
+
+pub type Pos<T = i64> = (T, T);
+pub type Pos3<T = i64> = (T, T, T);
+
+/// Arithmetic shared by 2D and 3D points.
+pub trait Point: Copy + Eq {
+    type Coord: Coord;
+
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn scale(self, k: Self::Coord) -> Self;
+    /// Sum of the absolute coordinates.
+    fn norm1(self) -> Self::Coord;
+}
+
+impl<T: Coord> Point for Pos<T> {
+    type Coord = T;
+
+    fn add(self, o: Self) -> Self {
+        (self.0 + o.0, self.1 + o.1)
+    }
+
+    fn sub(self, o: Self) -> Self {
+        (self.0 - o.0, self.1 - o.1)
+    }
+
+    fn scale(self, k: T) -> Self {
+        (self.0 * k, self.1 * k)
+    }
+
+    fn norm1(self) -> T {
+        self.0.abs() + self.1.abs()
+    }
+}
+
+impl<T: Coord> Point for Pos3<T> {
+    type Coord = T;
+
+    fn add(self, o: Self) -> Self {
+        (self.0 + o.0, self.1 + o.1, self.2 + o.2)
+    }
+
+    fn sub(self, o: Self) -> Self {
+        (self.0 - o.0, self.1 - o.1, self.2 - o.2)
+    }
+
+    fn scale(self, k: T) -> Self {
+        (self.0 * k, self.1 * k, self.2 * k)
+    }
+
+    fn norm1(self) -> T {
+        self.0.abs() + self.1.abs() + self.2.abs()
+    }
+}
+
+pub fn add<P: Point>(a: P, b: P) -> P {
+    a.add(b)
+}
+
+pub fn sub<P: Point>(a: P, b: P) -> P {
+    a.sub(b)
+}
+
+pub fn scale<P: Point>(p: P, k: P::Coord) -> P {
+    p.scale(k)
+}
+
+pub fn manhattan<P: Point>(a: P, b: P) -> P::Coord {
+    a.sub(b).norm1()
+}
+
+/// Offsets of the 4 orthogonal neighbours.
+pub static NLIST: [Pos; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the 8 neighbours, diagonals included.
+pub static NLIST8: [Pos; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+fn offset<T: Coord>(n: Pos) -> Pos<T> {
+    (T::from(n.0 as i8), T::from(n.1 as i8))
+}
+
+/// The 4 orthogonal neighbours of `p`.
+pub fn neighbours4<T: Coord>(p: Pos<T>) -> impl Iterator<Item = Pos<T>> {
+    NLIST.iter().map(move |&n| add(p, offset(n)))
+}
+
+/// The 8 neighbours of `p`, diagonals included.
+pub fn neighbours8<T: Coord>(p: Pos<T>) -> impl Iterator<Item = Pos<T>> {
+    NLIST8.iter().map(move |&n| add(p, offset(n)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    N,
+    S,
+    W,
+    E,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+    /// Parses `U`/`D`/`L`/`R`, `N`/`S`/`W`/`E` or the arrows `^`/`v`/`<`/`>`.
+    pub fn from_char(c: char) -> Option<Dir> {
+        match c {
+            'U' | 'N' | '^' => Some(Dir::N),
+            'D' | 'S' | 'v' => Some(Dir::S),
+            'L' | 'W' | '<' => Some(Dir::W),
+            'R' | 'E' | '>' => Some(Dir::E),
+            _ => None,
+        }
+    }
+
+    pub fn offset<T: Coord>(self) -> Pos<T> {
+        offset(match self {
+            Dir::N => (0, -1),
+            Dir::S => (0, 1),
+            Dir::W => (-1, 0),
+            Dir::E => (1, 0),
+        })
+    }
+
+    /// Position one step away from `p` in this direction.
+    pub fn step<T: Coord>(self, p: Pos<T>) -> Pos<T> {
+        add(p, self.offset())
+    }
+
+    /// Position `n` steps away from `p` in this direction.
+    pub fn steps<T: Coord>(self, p: Pos<T>, n: T) -> Pos<T> {
+        add(p, scale(self.offset(), n))
+    }
+
+    /// A quarter turn counter-clockwise.
+    pub fn left(self) -> Dir {
+        match self {
+            Dir::N => Dir::W,
+            Dir::W => Dir::S,
+            Dir::S => Dir::E,
+            Dir::E => Dir::N,
+        }
+    }
+
+    /// A quarter turn clockwise.
+    pub fn right(self) -> Dir {
+        match self {
+            Dir::N => Dir::E,
+            Dir::E => Dir::S,
+            Dir::S => Dir::W,
+            Dir::W => Dir::N,
+        }
+    }
+
+    pub fn opposite(self) -> Dir {
+        match self {
+            Dir::N => Dir::S,
+            Dir::S => Dir::N,
+            Dir::W => Dir::E,
+            Dir::E => Dir::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(add((1i16, 2), (3, -4)), (4, -2));
+        assert_eq!(sub((1, 2, 3), (1, 1, 1)), (0, 1, 2));
+        assert_eq!(scale((2i32, -3), 4), (8, -12));
+        assert_eq!(manhattan((0i64, 0), (-3, 4)), 7);
+        assert_eq!(manhattan((1, 2, 3), (0, 0, 0)), 6);
+    }
+
+    #[test]
+    fn neighbours() {
+        let n: Vec<Pos<i16>> = neighbours4((0, 0)).collect();
+        assert_eq!(n, [(-1, 0), (0, -1), (0, 1), (1, 0)]);
+        assert_eq!(neighbours8((5i32, 5)).count(), 8);
+    }
+
+    #[test]
+    fn directions() {
+        for d in Dir::ALL {
+            assert_eq!(d.left().right(), d);
+            assert_eq!(d.right().right(), d.opposite());
+            assert_eq!(add(d.offset::<i64>(), d.opposite().offset()), (0, 0));
+        }
+        assert_eq!(Dir::N.right(), Dir::E);
+        assert_eq!(Dir::E.step((0, 0)), (1, 0));
+        assert_eq!(Dir::S.steps((1, 1), 3), (1, 4));
+        assert_eq!(Dir::from_char('U'), Some(Dir::N));
+        assert_eq!(Dir::from_char('W'), Some(Dir::W));
+        assert_eq!(Dir::from_char('>'), Some(Dir::E));
+        assert_eq!(Dir::from_char('x'), None);
+    }
+}
@@ -0,0 +1,499 @@
+//! Graphs whose nodes are named by any hashable label, interned to dense ids
+//! so the algorithms work on plain indices.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::hash::Hash;
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    labels: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    /// Outgoing edges of each node with their weight, in insertion order.
+    out: Vec<Vec<(NodeId, u64)>>,
+    /// Sources of the edges into each node, only kept for directed graphs.
+    incoming: Vec<Vec<NodeId>>,
+}
+
+/// Two sides of a graph and the total weight of the edges between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub weight: u64,
+    /// Nodes on the side of the source.
+    pub side: Vec<NodeId>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn directed() -> Self {
+        Graph::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            labels: vec![],
+            ids: HashMap::new(),
+            out: vec![],
+            incoming: vec![],
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Id of the node labelled `label`, adding it if it's new.
+    pub fn node(&mut self, label: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.out.push(vec![]);
+        self.incoming.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, label: &N) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &N {
+        &self.labels[id]
+    }
+
+    pub fn labels(&self) -> &[N] {
+        &self.labels
+    }
+
+    pub fn add_edge(&mut self, a: N, b: N) -> (NodeId, NodeId) {
+        self.add_weighted_edge(a, b, 1)
+    }
+
+    /// Adds an edge from `a` to `b`, and back if the graph is undirected,
+    /// adding the nodes as needed.
+    pub fn add_weighted_edge(&mut self, a: N, b: N, weight: u64) -> (NodeId, NodeId) {
+        let (a, b) = (self.node(a), self.node(b));
+        self.out[a].push((b, weight));
+        if self.directed {
+            self.incoming[b].push(a);
+        } else if a != b {
+            self.out[b].push((a, weight));
+        }
+        (a, b)
+    }
+
+    /// Edges out of `id` with their weight, in the order they were added.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.out[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.out[id].iter().map(|e| e.0)
+    }
+
+    /// Nodes with an edge into `id`.
+    pub fn incoming(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let incoming: &[NodeId] = if self.directed {
+            &self.incoming[id]
+        } else {
+            &[]
+        };
+        let undirected = (!self.directed).then(|| self.neighbours(id));
+        incoming
+            .iter()
+            .copied()
+            .chain(undirected.into_iter().flatten())
+    }
+
+    /// Number of edges from `start` to every node, `None` if unreachable.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        dist[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            let d = dist[id].unwrap();
+            for n in self.neighbours(id) {
+                if dist[n].is_none() {
+                    dist[n] = Some(d + 1);
+                    queue.push_back(n);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Nodes reachable from `start` in depth-first preorder.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+            // reversed so the first edge is walked first
+            stack.extend(
+                self.neighbours(id)
+                    .filter(|&n| !seen[n])
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            );
+        }
+        order
+    }
+
+    /// Groups of nodes connected to each other, ignoring the direction of
+    /// edges.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = vec![];
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![];
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                component.push(id);
+                for n in self.neighbours(id).chain(self.incoming(id)) {
+                    if !seen[n] {
+                        seen[n] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Nodes ordered so every edge goes forwards, `None` if there's a cycle.
+    pub fn toposort(&self) -> Option<Vec<NodeId>> {
+        let mut indegree = vec![0; self.len()];
+        for edges in &self.out {
+            for &(n, _) in edges {
+                indegree[n] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<_> = (0..self.len()).filter(|&id| indegree[id] == 0).collect();
+        let mut order = vec![];
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for n in self.neighbours(id) {
+                indegree[n] -= 1;
+                if indegree[n] == 0 {
+                    queue.push_back(n);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Strongly connected components with Tarjan's algorithm, each one listed
+    /// before the components it has edges from.
+    pub fn sccs(&self) -> Vec<Vec<NodeId>> {
+        let n = self.len();
+        let mut index = vec![None; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        let mut next = 0;
+        let mut sccs = vec![];
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+            // each frame is a node and how many of its edges were followed
+            let mut calls = vec![(root, 0)];
+            index[root] = Some(next);
+            low[root] = next;
+            next += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (v, ref mut i)) = calls.last_mut() {
+                if let Some(&(w, _)) = self.out[v].get(*i) {
+                    *i += 1;
+                    match index[w] {
+                        None => {
+                            index[w] = Some(next);
+                            low[w] = next;
+                            next += 1;
+                            stack.push(w);
+                            on_stack[w] = true;
+                            calls.push((w, 0));
+                        }
+                        Some(iw) if on_stack[w] => low[v] = low[v].min(iw),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if Some(low[v]) == index[v] {
+                    let mut scc = vec![];
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w] = false;
+                        scc.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    scc.sort();
+                    sccs.push(scc);
+                }
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+            }
+        }
+        sccs
+    }
+
+    /// Lightest set of edges separating `s` from `t`, with the edge weights as
+    /// capacities, by Edmonds-Karp max flow.
+    pub fn min_cut(&self, s: NodeId, t: NodeId) -> Cut {
+        Residual::new(self)
+            .min_cut(s, t, u64::MAX)
+            .expect("a flow below the limit")
+    }
+
+    /// Lightest set of edges splitting an undirected graph in two, `None` with
+    /// fewer than two nodes.
+    pub fn global_min_cut(&self) -> Option<Cut> {
+        assert!(!self.directed, "global min cut needs an undirected graph");
+        // any cut puts some node on the other side from node 0, and only the
+        // flows lighter than the best cut so far need to be finished
+        let residual = Residual::new(self);
+        let mut best: Option<Cut> = None;
+        for t in 1..self.len() {
+            let limit = best.as_ref().map_or(u64::MAX, |cut| cut.weight);
+            if let Some(cut) = residual.min_cut(0, t, limit) {
+                best = Some(cut);
+            }
+        }
+        best
+    }
+}
+
+/// Arcs of a graph for max flow, stored in pairs so `i ^ 1` is the reverse
+/// of arc `i`.
+struct Residual {
+    to: Vec<NodeId>,
+    cap: Vec<u64>,
+    /// Arcs out of each node.
+    arcs: Vec<Vec<usize>>,
+}
+
+impl Residual {
+    fn new<N>(graph: &Graph<N>) -> Residual {
+        let mut to = vec![];
+        let mut cap = vec![];
+        let mut arcs = vec![vec![]; graph.labels.len()];
+        for (u, edges) in graph.out.iter().enumerate() {
+            for &(v, w) in edges {
+                arcs[u].push(to.len());
+                to.push(v);
+                cap.push(w);
+                arcs[v].push(to.len());
+                to.push(u);
+                cap.push(0);
+            }
+        }
+        Residual { to, cap, arcs }
+    }
+
+    /// Min cut between `s` and `t` by Edmonds-Karp, `None` once the flow
+    /// reaches `limit`.
+    fn min_cut(&self, s: NodeId, t: NodeId, limit: u64) -> Option<Cut> {
+        let n = self.arcs.len();
+        let mut cap = self.cap.clone();
+        let mut weight = 0;
+        while weight < limit {
+            let mut via = vec![None; n];
+            let mut seen = vec![false; n];
+            seen[s] = true;
+            let mut queue = VecDeque::from([s]);
+            while let Some(u) = queue.pop_front() {
+                for &a in &self.arcs[u] {
+                    let v = self.to[a];
+                    if cap[a] > 0 && !seen[v] {
+                        seen[v] = true;
+                        via[v] = Some(a);
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            if !seen[t] {
+                let side = (0..n).filter(|&id| seen[id]).collect();
+                return Some(Cut { weight, side });
+            }
+
+            let mut path = vec![];
+            let mut v = t;
+            while let Some(a) = via[v] {
+                path.push(a);
+                v = self.to[a ^ 1];
+            }
+            let flow = path.iter().map(|&a| cap[a]).min().unwrap();
+            for a in path {
+                cap[a] -= flow;
+                cap[a ^ 1] += flow;
+            }
+            weight = weight.saturating_add(flow);
+        }
+        None
+    }
+}
+
+impl<N: Clone + Eq + Hash + fmt::Display> Graph<N> {
+    /// Graphviz source drawing the graph, with the weights as edge labels
+    /// unless they're all 1, e.g. for `dot -Tsvg -o graph.svg`.
+    pub fn to_dot(&self, name: &str) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let weighted = self.out.iter().flatten().any(|e| e.1 != 1);
+
+        let mut s = String::new();
+        writeln!(s, "{kind} {} {{", quote(name)).unwrap();
+        for label in &self.labels {
+            writeln!(s, "    {};", quote(&label.to_string())).unwrap();
+        }
+        for (a, edges) in self.out.iter().enumerate() {
+            for &(b, w) in edges {
+                // undirected edges are stored both ways, draw them once
+                if !self.directed && b < a {
+                    continue;
+                }
+                let (a, b) = (
+                    quote(&self.labels[a].to_string()),
+                    quote(&self.labels[b].to_string()),
+                );
+                if weighted {
+                    writeln!(s, "    {a} {arrow} {b} [label={w}];").unwrap();
+                } else {
+                    writeln!(s, "    {a} {arrow} {b};").unwrap();
+                }
+            }
+        }
+        s.push_str("}\n");
+        s
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directed(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut g = Graph::directed();
+        for &(a, b) in edges {
+            g.add_edge(a, b);
+        }
+        g
+    }
+
+    #[test]
+    fn interning_and_traversal() {
+        let g = directed(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("e", "a")]);
+        assert_eq!(g.len(), 5);
+        let id = |l| g.id(&l).unwrap();
+        assert_eq!(g.label(id("c")), &"c");
+        assert_eq!(g.bfs(id("a"))[id("d")], Some(2));
+        assert_eq!(g.bfs(id("a"))[id("e")], None);
+        let labels: Vec<_> = g.dfs(id("a")).iter().map(|&i| *g.label(i)).collect();
+        assert_eq!(labels, ["a", "b", "d", "c"]);
+        let mut into_a: Vec<_> = g.incoming(id("d")).map(|i| *g.label(i)).collect();
+        into_a.sort();
+        assert_eq!(into_a, ["b", "c"]);
+    }
+
+    #[test]
+    fn orderings() {
+        let g = directed(&[("a", "b"), ("b", "c"), ("a", "c"), ("d", "c")]);
+        let order = g.toposort().unwrap();
+        let pos = |l| order.iter().position(|&i| g.label(i) == &l).unwrap();
+        assert!(pos("a") < pos("b") && pos("b") < pos("c") && pos("d") < pos("c"));
+
+        let g = directed(&[
+            ("a", "b"),
+            ("b", "a"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "c"),
+            ("e", "e"),
+        ]);
+        assert!(g.toposort().is_none());
+        let sccs: Vec<Vec<_>> = g
+            .sccs()
+            .iter()
+            .map(|c| c.iter().map(|&i| *g.label(i)).collect())
+            .collect();
+        assert_eq!(sccs, [vec!["c", "d"], vec!["a", "b"], vec!["e"]]);
+        assert_eq!(g.components().len(), 2);
+    }
+
+    #[test]
+    fn cuts() {
+        // two triangles joined by a single edge
+        let mut g = Graph::undirected();
+        for (a, b) in [(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4), (3, 4)] {
+            g.add_edge(a, b);
+        }
+        let cut = g.global_min_cut().unwrap();
+        assert_eq!(cut.weight, 1);
+        let mut side: Vec<_> = cut.side.iter().map(|&i| *g.label(i)).collect();
+        side.sort();
+        assert_eq!(side, [1, 2, 3]);
+
+        let (s, t) = (g.id(&1).unwrap(), g.id(&2).unwrap());
+        assert_eq!(g.min_cut(s, t).weight, 2);
+    }
+
+    #[test]
+    fn dot() {
+        let g = directed(&[("a", "b")]);
+        assert_eq!(
+            g.to_dot("G"),
+            "digraph \"G\" {\n    \"a\";\n    \"b\";\n    \"a\" -> \"b\";\n}\n"
+        );
+
+        let mut g = Graph::undirected();
+        g.add_weighted_edge("x", "y", 3);
+        assert!(g.to_dot("G").contains("\"x\" -- \"y\" [label=3];"));
+        assert_eq!(g.to_dot("G").matches("--").count(), 1);
+    }
+}
@@ -0,0 +1,346 @@
+//! Tile maps parsed from the usual character grids: the dense [`Grid`] for
+//! rectangular puzzles and the sparse [`TileMap`] for scattered points.
+
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{ParseError, ParseResult};
+use crate::geom::{add, Pos, NLIST, NLIST8};
+use crate::parse::{char_at, end};
+
+/// Rectangular grid of tiles stored row by row, indexed by `(x, y)` with
+/// `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid filled with `tile`.
+    pub fn new(width: usize, height: usize, tile: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![tile; width * height],
+        }
+    }
+
+    /// Builds a grid from its tiles in row order.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, p: Pos) -> bool {
+        (0..self.width as i64).contains(&p.0) && (0..self.height as i64).contains(&p.1)
+    }
+
+    fn idx(&self, p: Pos) -> Option<usize> {
+        self.in_bounds(p)
+            .then(|| p.1 as usize * self.width + p.0 as usize)
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        self.idx(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.idx(p).map(|i| &mut self.cells[i])
+    }
+
+    /// The tile at `p` on a grid repeating infinitely in every direction.
+    pub fn get_wrapping(&self, p: Pos) -> &T {
+        let x = p.0.rem_euclid(self.width as i64);
+        let y = p.1.rem_euclid(self.height as i64);
+        &self[(x, y)]
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (w, h) = (self.width as i64, self.height as i64);
+        (0..h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    /// Every position and its tile in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first tile matching `f` in row order.
+    pub fn find(&self, mut f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, t)| f(t)).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn col(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// In-bounds orthogonal neighbours of `p` with their tiles.
+    pub fn neighbours4(&self, p: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        NLIST.iter().filter_map(move |&n| {
+            let np = add(p, n);
+            self.get(np).map(|t| (np, t))
+        })
+    }
+
+    /// In-bounds neighbours of `p` with their tiles, diagonals included.
+    pub fn neighbours8(&self, p: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        NLIST8.iter().filter_map(move |&n| {
+            let np = add(p, n);
+            self.get(np).map(|t| (np, t))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.cols().flat_map(|c| c.cloned()).collect();
+        Grid::from_vec(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.cols().flat_map(|c| c.rev().cloned()).collect();
+        Grid::from_vec(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter counter-clockwise.
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.col(x).cloned())
+            .collect();
+        Grid::from_vec(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Pos) -> &T {
+        match self.get(p) {
+            Some(t) => t,
+            None => panic!(
+                "{p:?} out of bounds of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        let (w, h) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(t) => t,
+            None => panic!("{p:?} out of bounds of a {w}x{h} grid"),
+        }
+    }
+}
+
+/// Parses a rectangular grid of characters, `tiles` lists the characters
+/// allowed in it.
+pub fn parse_grid(s: &str, tiles: &str) -> ParseResult<Grid<char>> {
+    parse_grid_with(s, |c| {
+        if tiles.contains(c) {
+            Ok(c)
+        } else {
+            Err(one_of(tiles))
+        }
+    })
+}
+
+/// Parses a rectangular grid with `f` converting each character, `f` fails
+/// with a description of what it expected instead of `c`.
+pub fn parse_grid_with<T>(
+    s: &str,
+    mut f: impl FnMut(char) -> Result<T, String>,
+) -> ParseResult<Grid<T>> {
+    if s.trim().is_empty() {
+        return Err(ParseError::new("a grid", s));
+    }
+
+    let mut width = None;
+    let mut height = 0;
+    let mut cells = vec![];
+    for line in s.lines() {
+        let mut len = 0;
+        for (i, c) in line.char_indices() {
+            if width == Some(len) {
+                return Err(ParseError::new("end of line", char_at(line, i)));
+            }
+            cells.push(f(c).map_err(|expected| ParseError::new(expected, char_at(line, i)))?);
+            len += 1;
+        }
+        if *width.get_or_insert(len) != len {
+            return Err(ParseError::new(
+                format!("{} tiles", width.unwrap()),
+                end(line),
+            ));
+        }
+        height += 1;
+    }
+
+    Ok(Grid::from_vec(width.unwrap(), height, cells))
+}
+
+pub type TileMap<T = char> = HashMap<Pos, T>;
+
+/// Parses every character of `s` into a map keyed by `(x, y)`, `tiles` lists
+/// the characters allowed in the grid.
+pub fn parse_map(s: &str, tiles: &str) -> ParseResult<TileMap> {
+    parse_map_with(s, |c| {
+        if tiles.contains(c) {
+            Ok(Some(c))
+        } else {
+            Err(one_of(tiles))
+        }
+    })
+}
+
+/// Parses `s` keeping only the tiles for which `f` returns `Some`, `f` fails
+/// with a description of what it expected instead of `c`.
+pub fn parse_map_with<T>(
+    s: &str,
+    mut f: impl FnMut(char) -> Result<Option<T>, String>,
+) -> ParseResult<TileMap<T>> {
+    let mut tilemap = HashMap::new();
+
+    if s.trim().is_empty() {
+        return Err(ParseError::new("a grid", s));
+    }
+
+    for (y, line) in s.lines().enumerate() {
+        for (x, (i, c)) in line.char_indices().enumerate() {
+            match f(c) {
+                Ok(Some(t)) => {
+                    tilemap.insert((x as i64, y as i64), t);
+                }
+                Ok(None) => (),
+                Err(expected) => return Err(ParseError::new(expected, char_at(line, i))),
+            }
+        }
+    }
+    Ok(tilemap)
+}
+
+/// Describes the allowed `tiles` for a [`ParseError`].
+pub fn one_of(tiles: &str) -> String {
+    let tiles: Vec<_> = tiles
+        .chars()
+        .map(|c| format!("`{}`", c.escape_debug()))
+        .collect();
+    format!("one of {}", tiles.join(", "))
+}
+
+/// Top left and bottom right corners of the area covered by `map`.
+pub fn corners<T>(map: &TileMap<T>) -> (Pos, Pos) {
+    let min = (
+        map.keys().map(|p| p.0).min().unwrap(),
+        map.keys().map(|p| p.1).min().unwrap(),
+    );
+    let max = (
+        map.keys().map(|p| p.0).max().unwrap(),
+        map.keys().map(|p| p.1).max().unwrap(),
+    );
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        parse_grid("abc\ndef\n", "abcdef").unwrap()
+    }
+
+    #[test]
+    fn indexing() {
+        let g = grid();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(2, 1)], 'f');
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.get((0, -1)), None);
+        assert_eq!(*g.get_wrapping((-1, 2)), 'c');
+        assert_eq!(g.find(|&c| c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn iterators() {
+        let g = grid();
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.col(1).collect::<String>(), "be");
+        assert_eq!(g.cols().count(), 3);
+        let mut n: Vec<_> = g.neighbours4((0, 0)).map(|(_, &c)| c).collect();
+        n.sort();
+        assert_eq!(n, ['b', 'd']);
+        assert_eq!(g.neighbours8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid();
+        let rows =
+            |g: &Grid<char>| -> Vec<String> { g.rows().map(|r| r.iter().collect()).collect() };
+        assert_eq!(rows(&g.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(rows(&g.rotate_cw()), ["da", "eb", "fc"]);
+        assert_eq!(rows(&g.rotate_ccw()), ["cf", "be", "ad"]);
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+    }
+
+    #[test]
+    fn ragged() {
+        let input = "abc\nde\n";
+        let err = parse_grid(input, "abcde").unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        let input = "ab\ncde\n";
+        let err = parse_grid(input, "abcde").unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+    }
+}
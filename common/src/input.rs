@@ -0,0 +1,199 @@
+//! Finding and reading the puzzle input for a day.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a directory of `dayNN.txt` inputs.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+/// Year of the puzzles solved here.
+pub const YEAR: u16 = 2023;
+
+/// Where to read an input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(PathBuf::from(arg))
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "stdin"),
+            Source::Path(p) => write!(f, "{}", p.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No explicit source was given and none of the default paths exist.
+    NotFound {
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+    Io {
+        source: Source,
+        err: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound { tried, .. } => {
+                write!(f, "no input found, tried ")?;
+                for (i, p) in tried.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", p.display())?;
+                }
+                write!(f, " (pass a path, `-` for stdin, or set ${INPUTS_VAR})")
+            }
+            InputError::Io { source, err } => write!(f, "cannot read {source}: {err}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Io { err, .. } => Some(err),
+        }
+    }
+}
+
+/// The workspace this crate was built in, which holds the day directories,
+/// whatever the directory the binary runs from.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the common crate sits in the workspace")
+}
+
+/// Where the input of `day` of `year` downloaded from the puzzle server is
+/// kept: `$AOC_INPUTS/YYYY/dayNN.txt`, else `inputs/YYYY/dayNN.txt` from
+/// the workspace root.
+pub fn cache_path(year: u16, day: u8) -> PathBuf {
+    let dir =
+        std::env::var_os(INPUTS_VAR).map_or_else(|| workspace_root().join("inputs"), PathBuf::from);
+    dir.join(year.to_string()).join(format!("day{day:02}.txt"))
+}
+
+/// Paths tried in order when no source is given: `$AOC_INPUTS/dayNN.txt`,
+/// the downloaded input at [`cache_path`] and `dayNN/input` from the
+/// workspace root.
+pub fn default_paths(day: u8) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Some(dir) = std::env::var_os(INPUTS_VAR) {
+        paths.push(PathBuf::from(dir).join(format!("day{day:02}.txt")));
+    }
+    paths.push(cache_path(YEAR, day));
+    paths.push(workspace_root().join(format!("day{day:02}/input")));
+    paths
+}
+
+/// Picks the source for `day`, the given one or the first of `local` and the
+/// [`default_paths`] that exists.
+pub fn resolve(day: u8, source: Option<&Source>, local: &[PathBuf]) -> Result<Source, InputError> {
+    if let Some(source) = source {
+        return Ok(source.clone());
+    }
+    let mut tried = local.to_vec();
+    tried.extend(default_paths(day));
+    match tried.iter().find(|p| p.is_file()) {
+        Some(p) => Ok(Source::Path(p.clone())),
+        None => Err(InputError::NotFound { day, tried }),
+    }
+}
+
+pub fn read_source(source: &Source) -> Result<String, InputError> {
+    let res = match source {
+        Source::Stdin => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s).map(|_| s)
+        }
+        Source::Path(p) => std::fs::read_to_string(p),
+    };
+    res.map_err(|err| InputError::Io {
+        source: source.clone(),
+        err,
+    })
+}
+
+/// Reads the input of `day`, see [`resolve`].
+pub fn read(day: u8, source: Option<&Source>, local: &[PathBuf]) -> Result<String, InputError> {
+    read_source(&resolve(day, source, local)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn precedence() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+        fs::write(&b, "b\n").unwrap();
+        let local = [a.clone(), b.clone()];
+
+        // a given source wins, even one that doesn't exist
+        let given = Source::Path(dir.join("c.txt"));
+        assert_eq!(resolve(99, Some(&given), &local).unwrap(), given);
+        // else the first local path that exists
+        assert_eq!(resolve(99, None, &local).unwrap(), Source::Path(b.clone()));
+        fs::write(&a, "a\n").unwrap();
+        assert_eq!(read(99, None, &local).unwrap(), "a\n");
+
+        // the default paths come after the local ones
+        let err = resolve(99, None, &[dir.join("d.txt")]).unwrap_err();
+        let InputError::NotFound { day, tried } = &err else {
+            panic!("{err}");
+        };
+        assert_eq!(*day, 99);
+        assert_eq!(tried[0], dir.join("d.txt"));
+        assert_eq!(tried[1..], default_paths(99));
+        assert_eq!(tried.last(), Some(&workspace_root().join("day99/input")));
+        assert!(err.to_string().starts_with("no input found, tried "));
+
+        let err = read_source(&given).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with(&format!("cannot read {given}: ")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn downloads_by_year() {
+        let path = cache_path(2022, 7);
+        assert!(path.ends_with("2022/day07.txt"));
+        assert_ne!(path, cache_path(YEAR, 7));
+        assert!(default_paths(7).contains(&cache_path(YEAR, 7)));
+    }
+
+    #[test]
+    fn from_the_workspace() {
+        assert!(workspace_root().join("Cargo.toml").is_file());
+        assert!(workspace_root().join("common").is_dir());
+        if std::env::var_os(INPUTS_VAR).is_none() {
+            assert!(cache_path(YEAR, 7).starts_with(workspace_root()));
+        }
+    }
+}
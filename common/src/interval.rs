@@ -0,0 +1,319 @@
+//! Half-open integer intervals and sets of them, for puzzles that push whole
+//! ranges of values through piecewise rules instead of single values.
+
+use std::fmt;
+
+/// Integer type usable as the bounds of an [`Interval`].
+pub trait Bound: Copy + Ord + fmt::Debug {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+        })*
+    };
+}
+
+impl_bound!(i32, i64, u32, u64, usize);
+
+/// The values `start..end`, empty when `end <= start`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T = i64> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> fmt::Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /// The `len` values from `start`, cut short at `T::MAX`.
+    pub fn from_len(start: T, len: T) -> Self {
+        Interval::new(start, start.saturating_add(len))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Number of values, `None` if it doesn't fit in `T`.
+    pub fn len(&self) -> Option<T> {
+        if self.is_empty() {
+            Some(T::ZERO)
+        } else {
+            self.end.checked_sub(self.start)
+        }
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Values before `x` and values from `x` on.
+    pub fn split_at(&self, x: T) -> (Self, Self) {
+        let x = x.clamp(self.start, self.end.max(self.start));
+        (Interval::new(self.start, x), Interval::new(x, self.end))
+    }
+
+    /// Values not in `other`: what's left before it and after it.
+    pub fn difference(&self, other: &Self) -> (Self, Self) {
+        if other.is_empty() {
+            return (*self, Interval::new(self.end, self.end));
+        }
+        let (before, _) = self.split_at(other.start);
+        let (_, after) = self.split_at(other.end);
+        (before, after)
+    }
+
+    /// The smallest interval holding both, if they overlap or touch so that
+    /// nothing lies between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start > other.end || other.start > self.end {
+            return None;
+        }
+        Some(Interval::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    /// Moves every value by the offset that takes `from` to `to`, `None` if
+    /// a bound would overflow.
+    pub fn shift(&self, from: T, to: T) -> Option<Self> {
+        let map = |x: T| {
+            if to >= from {
+                x.checked_add(to.checked_sub(from)?)
+            } else {
+                x.checked_sub(from.checked_sub(to)?)
+            }
+        };
+        Some(Interval::new(map(self.start)?, map(self.end)?))
+    }
+}
+
+/// Values as a sorted list of disjoint, non-adjacent, non-empty intervals.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T = i64> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> fmt::Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+impl<T: Bound> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values, `None` if it doesn't fit in `T`.
+    pub fn len(&self) -> Option<T> {
+        self.intervals
+            .iter()
+            .try_fold(T::ZERO, |acc, iv| acc.checked_add(iv.len()?))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|iv| iv.start)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|iv| iv.end <= x);
+        self.intervals.get(i).is_some_and(|iv| iv.contains(x))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn insert(&mut self, iv: Interval<T>) {
+        if iv.is_empty() {
+            return;
+        }
+        // everything from the first interval ending at or after `iv` starts to
+        // the last one starting at or before it ends gets merged with it
+        let lo = self.intervals.partition_point(|x| x.end < iv.start);
+        let hi = self.intervals.partition_point(|x| x.start <= iv.end);
+        let merged = self.intervals[lo..hi]
+            .iter()
+            .fold(iv, |acc, x| acc.union(x).unwrap());
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    pub fn remove(&mut self, iv: Interval<T>) {
+        if iv.is_empty() {
+            return;
+        }
+        let lo = self.intervals.partition_point(|x| x.end <= iv.start);
+        let hi = self.intervals.partition_point(|x| x.start < iv.end);
+        let kept: Vec<_> = self.intervals[lo..hi]
+            .iter()
+            .flat_map(|x| {
+                let (before, after) = x.difference(&iv);
+                [before, after]
+            })
+            .filter(|x| !x.is_empty())
+            .collect();
+        self.intervals.splice(lo..hi, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for iv in &other.intervals {
+            set.insert(*iv);
+        }
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut set = RangeSet::new();
+        for a in &self.intervals {
+            for b in &other.intervals {
+                set.insert(a.intersect(b));
+            }
+        }
+        set
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for iv in &other.intervals {
+            set.remove(*iv);
+        }
+        set
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for iv in iter {
+            set.insert(iv);
+        }
+        set
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for RangeSet<T> {
+    fn from(iv: Interval<T>) -> Self {
+        RangeSet::from_iter([iv])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i64, end: i64) -> Interval {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn intervals() {
+        assert_eq!(iv(0, 10).intersect(&iv(5, 15)), iv(5, 10));
+        assert!(iv(0, 5).intersect(&iv(5, 10)).is_empty());
+        assert_eq!(iv(0, 10).difference(&iv(3, 5)), (iv(0, 3), iv(5, 10)));
+        let (before, after) = iv(0, 10).difference(&iv(-5, 20));
+        assert!(before.is_empty() && after.is_empty());
+        assert_eq!(iv(0, 10).split_at(4), (iv(0, 4), iv(4, 10)));
+        assert_eq!(iv(0, 10).split_at(20), (iv(0, 10), iv(10, 10)));
+        assert_eq!(iv(0, 5).union(&iv(5, 8)), Some(iv(0, 8)));
+        assert_eq!(iv(0, 5).union(&iv(6, 8)), None);
+        assert_eq!(iv(10, 20).shift(10, 50), Some(iv(50, 60)));
+        assert_eq!(iv(10, 20).shift(15, 0), Some(iv(-5, 5)));
+        assert_eq!(iv(0, 3).len(), Some(3));
+        assert_eq!(iv(3, 0).len(), Some(0));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(
+            Interval::from_len(u64::MAX - 1, 5),
+            Interval::new(u64::MAX - 1, u64::MAX)
+        );
+        assert_eq!(
+            Interval::new(5u64, 10).shift(5, 0),
+            Some(Interval::new(0, 5))
+        );
+        assert_eq!(Interval::new(5u64, 10).shift(6, 0), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), None);
+    }
+
+    #[test]
+    fn range_sets() {
+        let mut set: RangeSet = [iv(0, 5), iv(10, 15), iv(5, 7)].into_iter().collect();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [iv(0, 7), iv(10, 15)]
+        );
+        assert_eq!(set.len(), Some(12));
+
+        set.insert(iv(6, 11));
+        assert_eq!(set, RangeSet::from(iv(0, 15)));
+
+        set.remove(iv(3, 4));
+        set.remove(iv(10, 20));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [iv(0, 3), iv(4, 10)]
+        );
+        assert!(set.contains(0) && !set.contains(3) && set.contains(9) && !set.contains(10));
+        assert_eq!(set.min(), Some(0));
+
+        let other: RangeSet = [iv(2, 5), iv(8, 12)].into_iter().collect();
+        assert_eq!(
+            set.intersection(&other).iter().copied().collect::<Vec<_>>(),
+            [iv(2, 3), iv(4, 5), iv(8, 10)]
+        );
+        assert_eq!(
+            set.difference(&other).iter().copied().collect::<Vec<_>>(),
+            [iv(0, 2), iv(5, 8)]
+        );
+        assert_eq!(set.union(&other), RangeSet::from(iv(0, 12)));
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared between the daily puzzle solutions.
+
+pub mod combinator;
+pub mod cycle;
+pub mod error;
+pub mod generate;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod memory;
+pub mod parallel;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod rng;
+pub mod search;
+pub mod solution;
+pub mod toml;
+pub mod trace;
@@ -0,0 +1,180 @@
+//! Small number theory helpers: gcd and lcm for any unsigned width, extended
+//! Euclid, modular inverses and the Chinese remainder theorem.
+
+use std::ops::Rem;
+
+/// Unsigned integer type usable with [`gcd`] and [`lcm`].
+pub trait Unsigned: Copy + Eq + Rem<Output = Self> {
+    const ZERO: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn div(self, other: Self) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn div(self, other: Self) -> Self {
+                self / other
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while a != T::ZERO {
+        (a, b) = (b % a, a);
+    }
+    b
+}
+
+/// Least common multiple, `None` if it doesn't fit in `T`.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    a.div(gcd(a, b)).checked_mul(b)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y` and `g >= 0`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `a * b mod m` for `0 <= a, b < m`, without overflowing when the product
+/// doesn't fit.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+    let (mut a, mut b, mut acc) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            acc = (acc + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    acc
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inv(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves the system `x = r (mod m)` for every `(r, m)` in `congruences`,
+/// with moduli that need not be coprime.
+///
+/// Returns `(x, l)` where `l` is the lcm of the moduli and `x` in `0..l` is
+/// the only solution modulo `l`, or `None` if the congruences contradict each
+/// other or `l` overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let (mut x, mut l) = (0, 1);
+    for &(r, m) in congruences {
+        assert!(m > 0, "modulus must be positive");
+        let r = r.rem_euclid(m);
+
+        // x + l * k = r (mod m)  <=>  (l / g) * k = (r - x) / g (mod m / g)
+        let (g, _, _) = ext_gcd(l, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let m_g = m / g;
+        let inv = mod_inv(l / g, m_g)?;
+        let k = mul_mod((diff / g).rem_euclid(m_g), inv, m_g);
+
+        let next = (l / g).checked_mul(m)?;
+        x = (x + l.checked_mul(k)?).rem_euclid(next);
+        l = next;
+    }
+    Some((x, l))
+}
+
+/// First time at which every `(start, period)` in `cycles` fires at once,
+/// where each fires at `start`, `start + period`, `start + 2 * period` and so
+/// on. `None` if they never line up or the time doesn't fit in a `u64`.
+pub fn sync(cycles: &[(u64, u64)]) -> Option<u64> {
+    let congruences: Vec<_> = cycles
+        .iter()
+        .map(|&(start, period)| (start as i128, period as i128))
+        .collect();
+    let (x, l) = crt(&congruences)?;
+
+    // the solution modulo `l` may come before some of the cycles start
+    let latest = cycles.iter().map(|c| c.0 as i128).max().unwrap_or(0);
+    let t = if x < latest {
+        x + (latest - x + l - 1) / l * l
+    } else {
+        x
+    };
+    u64::try_from(t).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u32, 5), 5);
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(0u8, 3), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm(1u128 << 100, 3), Some(3 << 100));
+    }
+
+    #[test]
+    fn inverses() {
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        let big = (1i128 << 61) - 1;
+        let (x, l) = crt(&[(5, big), (7, big - 2)]).unwrap();
+        assert_eq!((x % big, x % (big - 2), l), (5, 7, big * (big - 2)));
+    }
+
+    #[test]
+    fn cycles_with_offsets() {
+        assert_eq!(sync(&[(2, 2), (3, 3)]), Some(6));
+        assert_eq!(sync(&[(7, 4), (5, 6)]), Some(11));
+        assert_eq!(sync(&[(13, 4), (5, 6)]), Some(17));
+        assert_eq!(sync(&[(0, 4), (1, 4)]), None);
+    }
+}
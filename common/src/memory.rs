@@ -0,0 +1,165 @@
+//! Counting what the solutions allocate, to find the ones worth making
+//! leaner.
+//!
+//! A binary opts in by making [`Counting`] its global allocator, after which
+//! [`measure`] tells how much a closure allocated. The counters are shared
+//! by every thread, so what runs at the same time on other threads is
+//! counted too: callers measuring parts run them one at a time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations and the bytes in use.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: aoc_common::memory::Counting = aoc_common::memory::Counting;
+/// ```
+pub struct Counting;
+
+impl Counting {
+    fn grown(size: usize) {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn shrunk(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::grown(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::grown(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Counting::shrunk(layout.size());
+    }
+
+    /// Counted as a new allocation of the new size, like the copy it
+    /// usually is.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            Counting::shrunk(layout.size());
+            Counting::grown(new_size);
+        }
+        new
+    }
+}
+
+/// Whether [`Counting`] is the global allocator, so far as it can tell:
+/// nothing is ever counted otherwise.
+pub fn counting() -> bool {
+    ALLOCS.load(Ordering::Relaxed) > 0
+}
+
+/// What a closure allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocs: usize,
+    pub bytes: usize,
+    /// Most bytes in use at once, over what was in use when it started.
+    pub peak: usize,
+}
+
+/// Runs `f` and tells what it allocated, `None` if allocations aren't
+/// [`counting`].
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<Usage>) {
+    if !counting() {
+        return (f(), None);
+    }
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let res = f();
+
+    let usage = Usage {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (res, Some(usage))
+}
+
+/// Formats `bytes` with three significant digits in the largest fitting
+/// binary unit.
+pub fn fmt_bytes(bytes: usize) -> String {
+    let mut v = bytes as f64;
+    let mut unit = "B";
+    for next in ["KiB", "MiB", "GiB"] {
+        if v < 1024.0 {
+            break;
+        }
+        v /= 1024.0;
+        unit = next;
+    }
+    if unit == "B" {
+        return format!("{bytes}{unit}");
+    }
+    let decimals = if v < 10.0 {
+        2
+    } else if v < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{v:.decimals$}{unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: Counting = Counting;
+
+    #[test]
+    fn measures() {
+        // other tests may allocate at the same time, so only the least
+        // can be checked
+        let (sum, usage) = measure(|| {
+            let big = vec![1u64; 1000];
+            let small: Vec<u64> = big.iter().map(|v| v * 2).take(10).collect();
+            big.iter().sum::<u64>() + small.iter().sum::<u64>()
+        });
+        let usage = usage.unwrap();
+
+        assert_eq!(sum, 1020);
+        assert!(counting());
+        assert!(usage.allocs >= 2);
+        assert!(usage.bytes >= 8080);
+        assert!(usage.peak >= 8080);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(fmt_bytes(0), "0B");
+        assert_eq!(fmt_bytes(1023), "1023B");
+        assert_eq!(fmt_bytes(1536), "1.50KiB");
+        assert_eq!(fmt_bytes(200 * 1024 * 1024), "200MiB");
+    }
+}
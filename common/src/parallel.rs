@@ -0,0 +1,119 @@
+//! Opt-in parallelism on std threads for loops whose steps don't depend on
+//! each other.
+//!
+//! Everything runs on the calling thread until [`set_threads`] asks for
+//! more. Results always come back in the order of the items, so answers are
+//! the same whatever the number of threads. Work started from inside a
+//! worker runs on that worker, so running days in parallel doesn't also
+//! split up the loops inside them.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+static THREADS: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    static WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs the parallel loops on up to `n` threads, at least 1.
+pub fn set_threads(n: usize) {
+    THREADS.store(n.max(1), Ordering::Relaxed);
+}
+
+/// Threads a parallel loop started here would use.
+pub fn threads() -> usize {
+    if WORKER.get() {
+        1
+    } else {
+        THREADS.load(Ordering::Relaxed)
+    }
+}
+
+/// Number of threads the machine can run at once, 1 if unknown.
+pub fn available() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// `f` of every item, in order. Items are handed out one at a time, so
+/// slow ones don't hold up a whole share of the work.
+pub fn map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let n = threads().min(items.len());
+    if n <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let done = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|s| {
+        for _ in 0..n {
+            s.spawn(|| {
+                WORKER.set(true);
+                let mut mine = vec![];
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    mine.push((i, f(item)));
+                }
+                done.lock().unwrap().extend(mine);
+            });
+        }
+    });
+
+    let mut done = done.into_inner().unwrap();
+    done.sort_unstable_by_key(|&(i, _)| i);
+    done.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Like [`map`], but lazy when running on one thread, so each result can
+/// be used as soon as it's ready.
+pub fn stream<'a, T, R>(
+    items: &'a [T],
+    f: impl Fn(&T) -> R + Sync + 'a,
+) -> impl Iterator<Item = R> + 'a
+where
+    T: Sync,
+    R: Send + 'a,
+{
+    let mut done = (threads() > 1).then(|| map(items, &f).into_iter());
+    items.iter().map(move |item| match &mut done {
+        Some(done) => done.next().unwrap(),
+        None => f(item),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_results_in_order() {
+        let items: Vec<u64> = (0..100).collect();
+        let slow_square = |&x: &u64| {
+            // uneven work so the threads finish out of order
+            thread::sleep(std::time::Duration::from_micros((x * 37) % 200));
+            x * x
+        };
+        let expected: Vec<_> = items.iter().map(slow_square).collect();
+
+        for n in [1, 2, 7] {
+            set_threads(n);
+            assert_eq!(map(&items, slow_square), expected);
+            assert_eq!(stream(&items, slow_square).collect::<Vec<_>>(), expected);
+        }
+
+        // nested loops stay on the worker
+        set_threads(4);
+        let nested = map(&[(); 4], |_| threads());
+        set_threads(1);
+        assert_eq!(nested, [1; 4]);
+    }
+}
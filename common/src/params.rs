@@ -0,0 +1,233 @@
+//! Puzzle constants a day lets callers change, to try variants of a puzzle
+//! or run it with the parameters of its examples.
+//!
+//! Each day declares its parameters with [`params!`](crate::params!), which
+//! gives a struct of public fields holding the puzzle's values by default,
+//! settable by name from [`Value`]s read off the command line or a config
+//! file.
+
+use std::fmt;
+
+use crate::toml::{Table, Value};
+
+/// Parameters by name, in declaration order.
+pub type Values = Vec<(&'static str, Value)>;
+
+pub trait Params: Default + Clone + fmt::Debug {
+    /// Names of the parameters, in declaration order.
+    const NAMES: &'static [&'static str];
+
+    /// Sets the parameter `name`, an error if there's none by that name or
+    /// `value` doesn't fit it.
+    fn set(&mut self, name: &str, value: &Value) -> Result<(), String>;
+
+    /// Current value of each parameter.
+    fn values(&self) -> Values;
+
+    /// Checks the parameters make sense together, once they're all set.
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The defaults with every value in `overrides` set.
+    fn with(overrides: &Table) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (name, value) in overrides {
+            params.set(name, value)?;
+        }
+        params.check()?;
+        Ok(params)
+    }
+}
+
+/// For the days without parameters.
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _value: &Value) -> Result<(), String> {
+        Err(format!("unknown parameter `{name}`, there are none"))
+    }
+
+    fn values(&self) -> Values {
+        vec![]
+    }
+}
+
+/// Type of a single parameter.
+pub trait ParamValue: Sized {
+    fn from_value(value: &Value) -> Option<Self>;
+    fn to_value(&self) -> Value;
+}
+
+macro_rules! param_value_int {
+    ($($t:ty),*) => {
+        $(
+            impl ParamValue for $t {
+                fn from_value(value: &Value) -> Option<Self> {
+                    match value {
+                        Value::Int(n) => (*n).try_into().ok(),
+                        _ => None,
+                    }
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::Int(*self as i128)
+                }
+            }
+        )*
+    };
+}
+
+param_value_int!(u8, u16, u32, u64, usize, i64);
+
+impl ParamValue for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+/// Error for a parameter `name` of type `T` that can't hold `value`.
+#[doc(hidden)]
+pub fn invalid<T>(name: &str, value: &Value) -> String {
+    format!(
+        "invalid value `{value}` for parameter `{name}`, expected a {}",
+        std::any::type_name::<T>()
+    )
+}
+
+/// Error for a parameter `name` that isn't one of `names`.
+#[doc(hidden)]
+pub fn unknown(name: &str, names: &[&str]) -> String {
+    let names: Vec<_> = names.iter().map(|n| format!("`{n}`")).collect();
+    format!(
+        "unknown parameter `{name}`, expected one of {}",
+        names.join(", ")
+    )
+}
+
+/// Declares a struct of parameters with their defaults and implements
+/// [`Params`] for it, with the [`check`](Params::check) that can follow:
+///
+/// ```
+/// aoc_common::params! {
+///     /// Cubes of each colour in the bag.
+///     pub struct Bag {
+///         pub red: u32 = 12,
+///         pub green: u32 = 13,
+///     }
+///
+///     fn check(&self) -> Result<(), String> {
+///         match self.red + self.green {
+///             0 => Err("the bag is empty".to_string()),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+        $(fn check(&$self_:ident) -> Result<(), String> $check:block)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, name: &str, value: &$crate::toml::Value) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = $crate::params::ParamValue::from_value(value)
+                            .ok_or_else(|| $crate::params::invalid::<$ty>(name, value))?;
+                    })*
+                    _ => return Err($crate::params::unknown(name, Self::NAMES)),
+                }
+                Ok(())
+            }
+
+            fn values(&self) -> $crate::params::Values {
+                vec![$((stringify!($field), $crate::params::ParamValue::to_value(&self.$field))),*]
+            }
+
+            $(fn check(&$self_) -> Result<(), String> $check)?
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        struct Example {
+            steps: usize = 64,
+            wrap: bool = false,
+        }
+
+        fn check(&self) -> Result<(), String> {
+            match self.steps {
+                0 => Err("`steps` must be at least 1".to_string()),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn defaults_and_overrides() {
+        assert_eq!(
+            Example::default().values(),
+            [("steps", Value::Int(64)), ("wrap", Value::Bool(false))]
+        );
+
+        let overrides = Table::from([
+            ("steps".to_string(), Value::Int(6)),
+            ("wrap".to_string(), Value::Bool(true)),
+        ]);
+        let params = Example::with(&overrides).unwrap();
+        assert_eq!((params.steps, params.wrap), (6, true));
+    }
+
+    #[test]
+    fn bad_overrides() {
+        let mut params = Example::default();
+        assert_eq!(
+            params.set("step", &Value::Int(6)).unwrap_err(),
+            "unknown parameter `step`, expected one of `steps`, `wrap`"
+        );
+        assert_eq!(
+            params.set("steps", &Value::Int(-1)).unwrap_err(),
+            "invalid value `-1` for parameter `steps`, expected a usize"
+        );
+        assert!(params.set("wrap", &Value::Int(1)).is_err());
+        assert!(().set("steps", &Value::Int(1)).is_err());
+
+        let overrides = Table::from([("steps".to_string(), Value::Int(0))]);
+        assert_eq!(
+            Example::with(&overrides).unwrap_err(),
+            "`steps` must be at least 1"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Slicing helpers that report a [`ParseError`] instead of panicking, for
+//! the few parsers not built from [`combinator`](crate::combinator)s.
+
+use crate::error::{ParseError, ParseResult};
+
+/// The empty slice at the end of `s`, for errors about missing input.
+pub fn end(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+/// The character of `s` starting at byte `i`, as a slice for error reporting.
+pub fn char_at(s: &str, i: usize) -> &str {
+    let len = s[i..].chars().next().map_or(0, char::len_utf8);
+    &s[i..i + len]
+}
+
+pub fn split_once<'a>(s: &'a str, delim: &str) -> ParseResult<(&'a str, &'a str)> {
+    s.split_once(delim)
+        .ok_or_else(|| ParseError::new(format!("`{}`", delim.escape_debug()), end(s)))
+}
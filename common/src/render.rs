@@ -0,0 +1,200 @@
+//! Pictures of a grid with highlighted cells, as plain text, text coloured
+//! with ANSI escapes or a PPM image, to see what a solution is doing.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::geom::{sub, Pos};
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const RED: Rgb = Rgb(230, 60, 50);
+    pub const GREEN: Rgb = Rgb(60, 200, 80);
+    pub const YELLOW: Rgb = Rgb(240, 210, 60);
+    pub const BLUE: Rgb = Rgb(70, 130, 240);
+    pub const CYAN: Rgb = Rgb(60, 210, 220);
+
+    /// Colour of a tile no overlay covers in an image.
+    fn of_tile(c: char) -> Rgb {
+        match c {
+            '.' | ' ' => Rgb(20, 20, 20),
+            '#' => Rgb(110, 110, 110),
+            _ => Rgb(180, 180, 180),
+        }
+    }
+}
+
+/// Cells highlighted on top of the tiles.
+#[derive(Debug, Clone)]
+struct Overlay {
+    color: Rgb,
+    glyph: Option<char>,
+    cells: HashSet<Pos>,
+}
+
+/// A grid of tiles and overlays on top of it, later ones drawn over earlier
+/// ones.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    tiles: Grid<char>,
+    /// Position of the top left tile, overlays are given relative to it.
+    origin: Pos,
+    overlays: Vec<Overlay>,
+}
+
+impl Canvas {
+    pub fn new(tiles: Grid<char>) -> Canvas {
+        Canvas {
+            tiles,
+            origin: (0, 0),
+            overlays: vec![],
+        }
+    }
+
+    /// `glyph` at every point, `.` around them, on the smallest grid holding
+    /// them all.
+    pub fn from_points(points: impl IntoIterator<Item = Pos>, glyph: char) -> Canvas {
+        let points: Vec<_> = points.into_iter().collect();
+        let min_x = points.iter().map(|p| p.0).min().unwrap_or(0);
+        let max_x = points.iter().map(|p| p.0).max().unwrap_or(-1);
+        let min_y = points.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_y = points.iter().map(|p| p.1).max().unwrap_or(-1);
+
+        let origin = (min_x, min_y);
+        let (w, h) = ((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+        let mut tiles = Grid::new(w, h, '.');
+        for p in points {
+            tiles[sub(p, origin)] = glyph;
+        }
+        Canvas {
+            tiles,
+            origin,
+            overlays: vec![],
+        }
+    }
+
+    /// Highlights `cells` in `color`, drawn as `glyph` in plain text, or as
+    /// the tile under them if `None`.
+    pub fn overlay(
+        mut self,
+        color: Rgb,
+        glyph: Option<char>,
+        cells: impl IntoIterator<Item = Pos>,
+    ) -> Canvas {
+        let cells = cells.into_iter().map(|p| sub(p, self.origin)).collect();
+        self.overlays.push(Overlay {
+            color,
+            glyph,
+            cells,
+        });
+        self
+    }
+
+    /// Tile at `pos` of the grid and the topmost overlay covering it.
+    fn cell(&self, pos: Pos) -> (char, Option<&Overlay>) {
+        let overlay = self.overlays.iter().rev().find(|o| o.cells.contains(&pos));
+        let tile = self.tiles[pos];
+        (overlay.and_then(|o| o.glyph).unwrap_or(tile), overlay)
+    }
+
+    pub fn to_ascii(&self) -> String {
+        let mut s = String::new();
+        for y in 0..self.tiles.height() as i64 {
+            for x in 0..self.tiles.width() as i64 {
+                s.push(self.cell((x, y)).0);
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Like [`Canvas::to_ascii`] with the overlays in bold colours, for a
+    /// terminal supporting 24-bit colour.
+    pub fn to_ansi(&self) -> String {
+        let mut s = String::new();
+        for y in 0..self.tiles.height() as i64 {
+            for x in 0..self.tiles.width() as i64 {
+                match self.cell((x, y)) {
+                    (c, Some(o)) => {
+                        let Rgb(r, g, b) = o.color;
+                        write!(s, "\x1b[1;38;2;{r};{g};{b}m{c}\x1b[0m").unwrap();
+                    }
+                    (c, None) => s.push(c),
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Writes a binary PPM image with each tile as a `scale` by `scale`
+    /// square.
+    pub fn write_ppm(&self, w: &mut impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+        write!(w, "P6\n{} {}\n255\n", width * scale, height * scale)?;
+
+        let mut row = Vec::with_capacity(width * scale * 3);
+        for y in 0..height as i64 {
+            row.clear();
+            for x in 0..width as i64 {
+                let Rgb(r, g, b) = match self.cell((x, y)) {
+                    (_, Some(o)) => o.color,
+                    (c, None) => Rgb::of_tile(c),
+                };
+                for _ in 0..scale {
+                    row.extend([r, g, b]);
+                }
+            }
+            for _ in 0..scale {
+                w.write_all(&row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::parse_grid;
+
+    #[test]
+    fn text() {
+        let canvas = Canvas::new(parse_grid("#..\n.#.\n", ".#").unwrap())
+            .overlay(Rgb::RED, Some('o'), [(1, 0), (2, 0)])
+            .overlay(Rgb::GREEN, None, [(2, 0), (1, 1)]);
+        // the green overlay is on top and shows the tile itself
+        assert_eq!(canvas.to_ascii(), "#o.\n.#.\n");
+
+        let ansi = canvas.to_ansi();
+        assert!(ansi.starts_with("#\x1b[1;38;2;230;60;50mo\x1b[0m\x1b[1;38;2;60;200;80m."));
+        assert!(ansi.contains(".\x1b[1;38;2;60;200;80m#\x1b[0m."));
+    }
+
+    #[test]
+    fn points() {
+        let canvas = Canvas::from_points([(-1, -1), (1, -1), (1, 0)], '#').overlay(
+            Rgb::BLUE,
+            Some('x'),
+            [(0, 0)],
+        );
+        assert_eq!(canvas.to_ascii(), "#.#\n.x#\n");
+    }
+
+    #[test]
+    fn image() {
+        let canvas =
+            Canvas::new(parse_grid("#.\n", ".#").unwrap()).overlay(Rgb(1, 2, 3), None, [(1, 0)]);
+        let mut out = vec![];
+        canvas.write_ppm(&mut out, 2).unwrap();
+
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        let row = [110, 110, 110, 110, 110, 110, 1, 2, 3, 1, 2, 3];
+        assert_eq!(&out[header.len()..], [row, row].concat());
+    }
+}
@@ -0,0 +1,295 @@
+//! Shortest path searches over any hashable state: breadth-first for unit
+//! steps, Dijkstra and A* for weighted ones.
+//!
+//! States are stored once and referred to by index, so they only need to be
+//! `Clone + Eq + Hash`, and the path to the goal is rebuilt from the parent
+//! of each state.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States taken off the frontier and expanded.
+    pub expanded: usize,
+    /// States added to the frontier, counting improved ones again.
+    pub pushed: usize,
+    /// Largest size of the frontier.
+    pub max_frontier: usize,
+}
+
+/// A cheapest way from one of the starts to a goal.
+#[derive(Debug, Clone)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// Every state from the start to the goal, both included.
+    pub states: Vec<S>,
+    pub stats: Stats,
+}
+
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+    closed: bool,
+}
+
+/// Known states, and the index of each in `nodes`.
+struct Nodes<S, C> {
+    nodes: Vec<Node<S, C>>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C> Nodes<S, C> {
+    fn new() -> Self {
+        Nodes {
+            nodes: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, state: S, parent: Option<usize>, cost: C) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(state.clone(), i);
+        self.nodes.push(Node {
+            state,
+            parent,
+            cost,
+            closed: false,
+        });
+        i
+    }
+
+    fn path(&self, mut i: usize) -> Vec<S> {
+        let mut states = vec![self.nodes[i].state.clone()];
+        while let Some(p) = self.nodes[i].parent {
+            states.push(self.nodes[p].state.clone());
+            i = p;
+        }
+        states.reverse();
+        states
+    }
+}
+
+/// A* from `starts` to the first state satisfying `goal`, where `successors`
+/// gives each next state with the cost of moving to it.
+///
+/// `heuristic` must never overestimate the cost left to a goal, and must be
+/// consistent for the first path found to be a cheapest one.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut nodes = Nodes::new();
+    let mut frontier = BinaryHeap::new();
+    let mut stats = Stats::default();
+
+    for s in starts {
+        if nodes.index.contains_key(&s) {
+            continue;
+        }
+        let h = heuristic(&s);
+        let i = nodes.insert(s, None, C::default());
+        frontier.push(Reverse((h, i)));
+        stats.pushed += 1;
+    }
+
+    while let Some(Reverse((_, i))) = frontier.pop() {
+        // improved states are pushed again, skip the stale entries
+        if nodes.nodes[i].closed {
+            continue;
+        }
+        nodes.nodes[i].closed = true;
+        stats.expanded += 1;
+
+        let cost = nodes.nodes[i].cost;
+        if goal(&nodes.nodes[i].state) {
+            return Some(Path {
+                cost,
+                states: nodes.path(i),
+                stats,
+            });
+        }
+
+        for (next, step) in successors(&nodes.nodes[i].state) {
+            let c = cost + step;
+            let j = match nodes.index.get(&next) {
+                Some(&j) if nodes.nodes[j].closed || nodes.nodes[j].cost <= c => continue,
+                Some(&j) => {
+                    nodes.nodes[j].cost = c;
+                    nodes.nodes[j].parent = Some(i);
+                    j
+                }
+                None => nodes.insert(next, Some(i), c),
+            };
+            frontier.push(Reverse((c + heuristic(&nodes.nodes[j].state), j)));
+            stats.pushed += 1;
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    None
+}
+
+/// Dijkstra from `starts` to the first state satisfying `goal`, where
+/// `successors` gives each next state with the cost of moving to it.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+/// Breadth-first search over every state at most `max` steps from `starts`,
+/// stopping early at the first one satisfying `goal`. Returns the known
+/// states and the goal's index among them.
+fn explore<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    max: usize,
+) -> (Nodes<S, usize>, Option<usize>, Stats)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut nodes = Nodes::new();
+    let mut frontier = VecDeque::new();
+    let mut stats = Stats::default();
+
+    for s in starts {
+        if !nodes.index.contains_key(&s) {
+            frontier.push_back(nodes.insert(s, None, 0));
+            stats.pushed += 1;
+        }
+    }
+
+    while let Some(i) = frontier.pop_front() {
+        stats.expanded += 1;
+        if goal(&nodes.nodes[i].state) {
+            return (nodes, Some(i), stats);
+        }
+
+        let d = nodes.nodes[i].cost;
+        if d == max {
+            continue;
+        }
+        for next in successors(&nodes.nodes[i].state) {
+            if !nodes.index.contains_key(&next) {
+                frontier.push_back(nodes.insert(next, Some(i), d + 1));
+                stats.pushed += 1;
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    (nodes, None, stats)
+}
+
+/// Breadth-first search from `starts` to the first state satisfying `goal`,
+/// where every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (nodes, found, stats) = explore(starts, successors, goal, usize::MAX);
+    found.map(|i| Path {
+        cost: nodes.nodes[i].cost,
+        states: nodes.path(i),
+        stats,
+    })
+}
+
+/// Number of steps to every state at most `max` steps from `starts`.
+pub fn distances<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    max: usize,
+) -> (HashMap<S, usize>, Stats)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (nodes, _, stats) = explore(starts, successors, |_| false, max);
+    let dist = nodes.nodes.into_iter().map(|n| (n.state, n.cost)).collect();
+    (dist, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::{manhattan, neighbours4, Pos};
+    use crate::grid::parse_grid;
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+";
+
+    #[test]
+    fn shortest_paths_agree() {
+        let map = parse_grid(MAZE, ".#").unwrap();
+        let goal: Pos = (6, 3);
+        let open = |p: &Pos| neighbours4(*p).filter(|n| map.get(*n) == Some(&'.'));
+
+        let b = bfs([(0, 0)], open, |p| *p == goal).unwrap();
+        let d = dijkstra([(0, 0)], |p| open(p).map(|n| (n, 1)), |p| *p == goal).unwrap();
+        let a = astar(
+            [(0, 0)],
+            |p| open(p).map(|n| (n, 1)),
+            |p| manhattan(*p, goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+
+        assert_eq!((b.cost, d.cost, a.cost), (11, 11, 11));
+        assert_eq!(b.states.len(), 12);
+        assert_eq!(b.states[0], (0, 0));
+        for path in [&b.states, &d.states, &a.states] {
+            assert!(path.windows(2).all(|w| manhattan(w[0], w[1]) == 1));
+        }
+        assert!(a.stats.expanded <= d.stats.expanded);
+    }
+
+    #[test]
+    fn weighted_and_unreachable() {
+        // going straight to 10 costs 10, going through 1 costs 2 + 3
+        let edges = |n: &u8| match n {
+            0 => vec![(10, 10), (1, 2)],
+            1 => vec![(10, 3)],
+            _ => vec![],
+        };
+        let path = dijkstra([0u8], edges, |n| *n == 10).unwrap();
+        assert_eq!((path.cost, path.states), (5, vec![0, 1, 10]));
+        assert!(dijkstra([0u8], edges, |n| *n == 7).is_none());
+    }
+
+    #[test]
+    fn distances_up_to_a_limit() {
+        let (dist, stats) = distances([0i32], |n| [n - 1, n + 1], 3);
+        assert_eq!(dist.len(), 7);
+        assert_eq!(dist[&-3], 3);
+        assert_eq!(stats.pushed, 7);
+    }
+}
@@ -0,0 +1,209 @@
+//! The interface every day implements, so solutions can be driven in-process.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::error::ParseResult;
+use crate::input::{self, Source};
+use crate::memory::{self, Usage};
+use crate::params::Params;
+use crate::render::Canvas;
+use crate::rng::Rng;
+use crate::trace;
+
+/// Answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u16, u32, u64, usize, i32, i64);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+pub trait Solution {
+    const DAY: u8;
+    /// Number of parts the puzzle has, day 25 only has one.
+    const PARTS: u8 = 2;
+    /// Kinds of the events the solution emits with [`trace!`](crate::trace!),
+    /// for the days worth following step by step.
+    const EVENTS: &'static [&'static str] = &[];
+
+    /// Puzzle input parsed once and shared between both parts.
+    type Model<'a>;
+
+    /// Constants of the puzzle text, `()` if there are none worth changing.
+    type Params: Params;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>>;
+
+    /// Checks `model` has what `part` needs with `params`, for the days
+    /// whose inputs can suit one part but not the other. Only looks at the
+    /// shape of the input, what takes solving to find out is reported by the
+    /// part itself.
+    fn check(_model: &Self::Model<'_>, _part: u8, _params: &Self::Params) -> ParseResult<()> {
+        Ok(())
+    }
+
+    /// Answer to part 1, an error pointing into the input if solving shows
+    /// it breaks a promise of the puzzle.
+    fn part1(model: &Self::Model<'_>, params: &Self::Params) -> ParseResult<Answer>;
+
+    /// Answer to part 2, like [`Solution::part1`].
+    fn part2(_model: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        unreachable!("day {} has no part 2", Self::DAY)
+    }
+
+    /// A made up input growing with `size`, at least 1, which keeps the
+    /// promises the puzzle makes about its inputs so that both parts can be
+    /// solved.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// Picture of what the solution does, for the days where one helps.
+    fn render(_model: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        None
+    }
+}
+
+/// Answers of one [`solve`] call, with the time each phase took.
+#[derive(Debug)]
+pub struct Solved {
+    pub answers: Vec<Answer>,
+    pub parse_time: Duration,
+    /// Time of each part, in the order they were asked for.
+    pub part_times: Vec<Duration>,
+    /// What each part allocated, `None` unless [`memory::Counting`] is the
+    /// global allocator.
+    pub part_usage: Option<Vec<Usage>>,
+}
+
+/// Parses `input` once and solves each of the requested `parts` in order.
+pub fn solve<S: Solution>(input: &str, parts: &[u8], params: &S::Params) -> ParseResult<Solved> {
+    let start = Instant::now();
+    let model = S::parse(input).map_err(|e| e.locate(input).with_day(S::DAY))?;
+    for &part in parts {
+        trace::quiet(|| S::check(&model, part, params))
+            .map_err(|e| e.locate(input).with_day(S::DAY))?;
+    }
+    let parse_time = start.elapsed();
+
+    let mut answers = vec![];
+    let mut part_times = vec![];
+    let mut part_usage = vec![];
+    for part in parts {
+        let start = Instant::now();
+        let (answer, usage) = memory::measure(|| match part {
+            1 => S::part1(&model, params),
+            2 => S::part2(&model, params),
+            _ => panic!("day {} has no part {part}", S::DAY),
+        });
+        part_times.push(start.elapsed());
+        answers.push(answer.map_err(|e| e.locate(input).with_day(S::DAY))?);
+        part_usage.push(usage);
+    }
+
+    Ok(Solved {
+        answers,
+        parse_time,
+        part_times,
+        part_usage: part_usage.into_iter().collect(),
+    })
+}
+
+/// Answers to every part of `input` with the default parameters, for tests
+/// of days whose answers are numbers. Panics if it can't be solved.
+pub fn answers<S: Solution>(input: &str) -> Vec<i128> {
+    let parts: Vec<_> = (1..=S::PARTS).collect();
+    match solve::<S>(input, &parts, &S::Params::default()) {
+        Ok(solved) => solved
+            .answers
+            .into_iter()
+            .map(|a| match a {
+                Answer::Int(n) => n,
+                Answer::Text(s) => panic!("`{s}` isn't a number"),
+            })
+            .collect(),
+        Err(e) => panic!("{e}\n{input}"),
+    }
+}
+
+/// The input generated from each of `seeds` with its [`answers`], for tests.
+pub fn solve_generated<S: Solution>(seeds: Range<u64>, size: usize) -> Vec<(String, Vec<i128>)> {
+    seeds
+        .map(|seed| {
+            let input = S::generate(&mut Rng::new(seed), size);
+            let answers = answers::<S>(&input);
+            (input, answers)
+        })
+        .collect()
+}
+
+/// Parses `input` and draws it with [`Solution::render`].
+pub fn render<S: Solution>(input: &str, params: &S::Params) -> ParseResult<Option<Canvas>> {
+    let model = S::parse(input).map_err(|e| e.locate(input).with_day(S::DAY))?;
+    Ok(S::render(&model, params))
+}
+
+/// Entry point of the per-day binaries, prints the answers to every part of
+/// `S` with its default parameters. The input is the path given as first argument (`-` for stdin), else
+/// the `input` file in the current directory, else the usual
+/// [`input::default_paths`].
+pub fn main<S: Solution>() -> ExitCode {
+    let source = std::env::args().nth(1).map(|a| Source::from_arg(&a));
+    let input = match input::read(S::DAY, source.as_ref(), &[PathBuf::from("input")]) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts: Vec<_> = (1..=S::PARTS).collect();
+    match solve::<S>(&input, &parts, &S::Params::default()) {
+        Ok(solved) => {
+            for (part, answer) in parts.iter().zip(solved.answers) {
+                println!("Part {part}: {answer}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
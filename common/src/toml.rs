@@ -0,0 +1,224 @@
+//! Reader and writer for the small subset of TOML used by the runner's
+//! files: `[section]` headers, `key = value` pairs with integer, string or
+//! boolean values, and `#` comments.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::{ParseError, ParseResult};
+use crate::parse::{end, split_once};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i128),
+    Str(String),
+    Bool(bool),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Str(s) => write!(f, "{s}"),
+            Value::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+pub type Table = BTreeMap<String, Value>;
+
+/// Sections by name, keys before the first header go in the `""` section.
+pub type Document = BTreeMap<String, Table>;
+
+fn is_bare(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Drops a trailing comment, ignoring `#` inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_str = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_str => escaped = true,
+            '"' => in_str = !in_str,
+            '#' if !in_str => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_string(s: &str) -> ParseResult<String> {
+    let body = &s[1..];
+    let mut out = String::new();
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' if !body[i + 1..].is_empty() => {
+                return Err(ParseError::new("end of line", &body[i + 1..]))
+            }
+            '"' => return Ok(out),
+            '\\' => match chars.next() {
+                Some((_, '"')) => out.push('"'),
+                Some((_, '\\')) => out.push('\\'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 't')) => out.push('\t'),
+                Some((j, _)) => return Err(ParseError::new("an escape", &body[i..=j])),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err(ParseError::new("`\"`", end(s)))
+}
+
+pub fn parse_value(s: &str) -> ParseResult<Value> {
+    match s {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if s.starts_with('"') {
+        return parse_string(s).map(Value::Str);
+    }
+    s.replace('_', "")
+        .parse()
+        .map(Value::Int)
+        .map_err(|_| ParseError::new("a number, string or boolean", s))
+}
+
+pub fn parse(input: &str) -> ParseResult<Document> {
+    let mut doc = Document::new();
+    let mut section = String::new();
+    doc.insert(section.clone(), Table::new());
+
+    for line in input.lines() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .ok_or_else(|| ParseError::new("`]`", end(line)))?
+                .trim();
+            if !is_bare(name) {
+                return Err(ParseError::new("a section name", name));
+            }
+            if doc.contains_key(name) {
+                return Err(ParseError::new("a new section", name));
+            }
+            section = name.to_string();
+            doc.insert(section.clone(), Table::new());
+            continue;
+        }
+
+        let (key, value) = split_once(line, "=")?;
+        let (key, value) = (key.trim(), value.trim());
+        if !is_bare(key) {
+            return Err(ParseError::new("a key", key));
+        }
+        let table = doc.get_mut(&section).unwrap();
+        if table.contains_key(key) {
+            return Err(ParseError::new("a new key", key));
+        }
+        table.insert(key.to_string(), parse_value(value)?);
+    }
+
+    Ok(doc)
+}
+
+/// `value` as it's written in a file, strings quoted and escaped.
+fn quote(value: &Value) -> String {
+    match value {
+        Value::Str(s) => {
+            let mut out = String::from('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\t' => out.push_str("\\t"),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+            out
+        }
+        v => v.to_string(),
+    }
+}
+
+/// Writes `doc` so that [`parse`] reads it back the same, the `""` section
+/// first and without a header.
+pub fn write(doc: &Document) -> String {
+    let mut out = String::new();
+    for (name, table) in doc {
+        if !name.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{name}]\n"));
+        }
+        for (key, value) in table {
+            out.push_str(&format!("{key} = {}\n", quote(value)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_and_values() {
+        let doc = parse(
+            "\
+top = 1
+# comment
+[day01]
+part1 = 54_632 # trailing
+part2 = \"a # b\"
+
+[day02]
+flag = true
+",
+        )
+        .unwrap();
+        assert_eq!(doc[""]["top"], Value::Int(1));
+        assert_eq!(doc["day01"]["part1"], Value::Int(54632));
+        assert_eq!(doc["day01"]["part2"], Value::Str("a # b".to_string()));
+        assert_eq!(doc["day02"]["flag"], Value::Bool(true));
+    }
+
+    #[test]
+    fn round_trip() {
+        let doc = parse("top = 1\n[day01]\nwrong = \"1 \\\"2\\\"\\n\"\nok = false\n").unwrap();
+        let text = write(&doc);
+        assert_eq!(
+            text,
+            "top = 1\n\n[day01]\nok = false\nwrong = \"1 \\\"2\\\"\\n\"\n"
+        );
+        assert_eq!(parse(&text).unwrap(), doc);
+    }
+
+    #[test]
+    fn errors_are_located() {
+        let input = "[day01]\npart1 = 1\npart1 = 2\n";
+        let err = parse(input).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(3), Some(1)));
+
+        let input = "[day01]\npart1 = nope\n";
+        let err = parse(input).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(2), Some(9)));
+
+        assert!(parse("[day01\n").is_err());
+        assert!(parse("x = \"open\n").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Events simulations emit as they go, to follow what they do step by step
+//! without adding prints.
+//!
+//! A day emits events with [`trace!`](crate::trace!), which does nothing
+//! but check a flag unless something is [recording](record) on the same
+//! thread. Each event has a kind, like `pulse`, and named fields already
+//! formatted, like `to=rx`, that a [`Filter`] can pick them by.
+
+use std::cell::{Cell, RefCell};
+
+use crate::geom::Dir;
+
+/// One thing that happened in a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// How a value is written in an event, the way a filter matches it.
+pub trait Field {
+    fn text(&self) -> String;
+}
+
+macro_rules! field_from_display {
+    ($($t:ty),*) => {
+        $(
+            impl Field for $t {
+                fn text(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+field_from_display!(i16, i32, i64, u8, u16, u32, u64, usize, bool, char, str, String);
+
+impl<T: Field + ?Sized> Field for &T {
+    fn text(&self) -> String {
+        (**self).text()
+    }
+}
+
+/// Positions are written `x,y`, like in the inputs.
+impl<T: Field> Field for (T, T) {
+    fn text(&self) -> String {
+        format!("{},{}", self.0.text(), self.1.text())
+    }
+}
+
+impl<T: Field> Field for (T, T, T) {
+    fn text(&self) -> String {
+        format!("{},{},{}", self.0.text(), self.1.text(), self.2.text())
+    }
+}
+
+impl Field for Dir {
+    fn text(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+type Sink = Box<dyn FnMut(&Event)>;
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Whether events emitted now would be recorded.
+pub fn enabled() -> bool {
+    RECORDING.get()
+}
+
+/// Hands `event` to the recorder, prefer [`trace!`](crate::trace!) which
+/// doesn't build it when nothing records.
+pub fn emit(event: Event) {
+    SINK.with_borrow_mut(|sink| {
+        if let Some(sink) = sink {
+            sink(&event);
+        }
+    });
+}
+
+/// Runs `f`, handing every event it emits on this thread to `sink`.
+/// Events emitted from other threads, like the workers of
+/// [`parallel`](crate::parallel), are lost.
+pub fn record<R>(sink: impl FnMut(&Event) + 'static, f: impl FnOnce() -> R) -> R {
+    let outer = SINK.replace(Some(Box::new(sink)));
+    RECORDING.set(true);
+    let res = f();
+    RECORDING.set(outer.is_some());
+    SINK.set(outer);
+    res
+}
+
+/// Runs `f` without recording the events it emits, for simulations run
+/// again to check an input before solving it.
+pub fn quiet<R>(f: impl FnOnce() -> R) -> R {
+    let recording = RECORDING.replace(false);
+    let res = f();
+    RECORDING.set(recording);
+    res
+}
+
+/// Emits an event of the given kind with `name = value` fields, the
+/// values being [`Field`]s. They're only evaluated while recording.
+///
+/// ```
+/// aoc_common::trace!("pulse", from = "broadcaster", to = "a", high = false);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($kind:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit($crate::trace::Event {
+                kind: $kind,
+                fields: vec![$((stringify!($name), $crate::trace::Field::text(&$value))),*],
+            });
+        }
+    };
+}
+
+/// Which events to keep: of one of the kinds, all if none are given, and
+/// with all the field values.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub kinds: Vec<String>,
+    pub fields: Vec<(String, String)>,
+}
+
+impl Filter {
+    pub fn matches(&self, event: &Event) -> bool {
+        (self.kinds.is_empty() || self.kinds.iter().any(|k| k == event.kind))
+            && self
+                .fields
+                .iter()
+                .all(|(name, value)| event.field(name) == Some(value.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn run(n: i64) -> i64 {
+        for i in 0..n {
+            trace!("step", i = i, at = (i, -i), even = i % 2 == 0);
+        }
+        n
+    }
+
+    #[test]
+    fn records_while_asked() {
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        let res = record(move |e| sink.borrow_mut().push(e.clone()), || run(3));
+
+        assert_eq!(res, 3);
+        let events = events.borrow();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[1],
+            Event {
+                kind: "step",
+                fields: vec![
+                    ("i", "1".to_string()),
+                    ("at", "1,-1".to_string()),
+                    ("even", "false".to_string())
+                ],
+            }
+        );
+
+        // not recording anymore
+        assert!(!enabled());
+        run(3);
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn quiet_inside_record() {
+        let events = Rc::new(RefCell::new(vec![]));
+        let sink = Rc::clone(&events);
+        record(
+            move |e| sink.borrow_mut().push(e.clone()),
+            || {
+                quiet(|| run(2));
+                run(1);
+            },
+        );
+        assert_eq!(events.borrow().len(), 1);
+    }
+
+    #[test]
+    fn filters() {
+        let event = Event {
+            kind: "pulse",
+            fields: vec![("to", "rx".to_string()), ("high", "false".to_string())],
+        };
+        let filter = |kinds: &[&str], fields: &[(&str, &str)]| Filter {
+            kinds: kinds.iter().map(|k| k.to_string()).collect(),
+            fields: fields
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+                .collect(),
+        };
+
+        assert!(filter(&[], &[]).matches(&event));
+        assert!(filter(&["split", "pulse"], &[("to", "rx")]).matches(&event));
+        assert!(!filter(&["split"], &[]).matches(&event));
+        assert!(!filter(&[], &[("to", "rx"), ("high", "true")]).matches(&event));
+        assert!(!filter(&[], &[("from", "rx")]).matches(&event));
+    }
+}
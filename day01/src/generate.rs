@@ -0,0 +1,49 @@
+//! Calibration documents of `size` lines.
+
+use aoc_common::rng::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..rng.index(1..8) {
+            match rng.below(3) {
+                0 => line.push_str(WORDS[rng.index(0..WORDS.len())]),
+                1 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                _ => {
+                    for _ in 0..rng.index(1..4) {
+                        line.push(char::from(b'a' + rng.below(26) as u8));
+                    }
+                }
+            }
+        }
+        // every line has at least one plain digit
+        let at = rng.index(0..line.len() + 1);
+        line.insert(at, char::from(b'1' + rng.below(9) as u8));
+        s.push_str(&line);
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::rng::Rng;
+    use aoc_common::solution::answers;
+
+    use crate::Day01;
+
+    #[test]
+    fn two_digit_values() {
+        for seed in 0..20 {
+            for line in super::input(&mut Rng::new(seed), 50).lines() {
+                let values = answers::<Day01>(line);
+                assert!(values.iter().all(|v| (11..=99).contains(v)), "{line}");
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use aoc_common::combinator::{lines, parse_all, word, Parser};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+const DIGITS: [&str; 18] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
+    "5", "6", "7", "8", "9",
+];
+
+/// One line of the document, with the value each part reads in it.
+#[derive(Debug, Clone)]
+pub struct Calibration<'a> {
+    line: &'a str,
+    /// From the first and last digit, `None` if the line only spells them.
+    plain: Option<u32>,
+    /// From the first and last digit or spelled digit.
+    spelled: u32,
+}
+
+impl Calibration<'_> {
+    fn new(line: &str) -> ParseResult<Calibration<'_>> {
+        let first = line.chars().find_map(|c| c.to_digit(10));
+        let last = line.chars().rev().find_map(|c| c.to_digit(10));
+        let plain = first.zip(last).map(|(first, last)| first * 10 + last);
+
+        let digit_at = |i: usize| {
+            DIGITS
+                .iter()
+                .position(|&d| line[i..].starts_with(d))
+                .map(|idx| (idx % 9) as u32 + 1)
+        };
+        let first = (0..line.len()).find_map(digit_at);
+        let last = (0..line.len()).rev().find_map(digit_at);
+        let Some((first, last)) = first.zip(last) else {
+            return Err(ParseError::new("a digit or a spelled digit", line));
+        };
+
+        Ok(Calibration {
+            line,
+            plain,
+            spelled: first * 10 + last,
+        })
+    }
+}
+
+fn p1(calibrations: &[Calibration]) -> u32 {
+    // every line has a plain digit once checked for part 1
+    calibrations.iter().filter_map(|c| c.plain).sum()
+}
+
+fn p2(calibrations: &[Calibration]) -> u32 {
+    calibrations.iter().map(|c| c.spelled).sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Model<'a> = Vec<Calibration<'a>>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(lines(word().and_then(Calibration::new)), input)
+    }
+
+    /// The example of part 2 spells all the digits of some lines, which
+    /// part 1 can't read.
+    fn check(calibrations: &Self::Model<'_>, part: u8, _params: &Self::Params) -> ParseResult<()> {
+        match calibrations.iter().find(|c| part == 1 && c.plain.is_none()) {
+            Some(c) => Err(ParseError::new("a digit", c.line)),
+            None => Ok(()),
+        }
+    }
+
+    fn part1(calibrations: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(calibrations).into())
+    }
+
+    fn part2(calibrations: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(calibrations).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrst6teen
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day01::parse(EXAMPLE1).unwrap()), 142);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day01::parse(EXAMPLE2).unwrap()), 281);
+    }
+
+    #[test]
+    fn missing_digits() {
+        let err = Day01::parse(
+            "a1b
+xyz
+",
+        )
+        .unwrap_err();
+        assert_eq!(err.expected, "a digit or a spelled digit");
+
+        let model = Day01::parse(EXAMPLE2).unwrap();
+        assert!(Day01::check(&model, 2, &()).is_ok());
+        let err = Day01::check(&model, 1, &()).unwrap_err().locate(EXAMPLE2);
+        assert_eq!(err.line, Some(2));
+    }
+}
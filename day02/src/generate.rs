@@ -0,0 +1,48 @@
+//! Records of `size` games.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for id in 1..=size {
+        let rounds: Vec<_> = (0..rng.index(1..7))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let cubes: Vec<_> = colors[..rng.index(1..4)]
+                    .iter()
+                    .map(|c| format!("{} {c}", rng.range(1..21)))
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        s.push_str(&format!("Game {id}: {}\n", rounds.join("; ")));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::{answers, solve_generated};
+
+    use crate::Day02;
+
+    /// Games are numbered from 1 and only count on their own, with at most
+    /// 20 cubes of each colour at a time.
+    #[test]
+    fn games_apart() {
+        for (input, sums) in solve_generated::<Day02>(0..20, 50) {
+            let mut total = [0, 0];
+            for (id, line) in (1..).zip(input.lines()) {
+                let [possible, power] = answers::<Day02>(line)[..] else {
+                    panic!("two parts");
+                };
+                assert!(possible == 0 || possible == id, "{line}");
+                assert!(power <= 20 * 20 * 20, "{line}");
+                total[0] += possible;
+                total[1] += power;
+            }
+            assert_eq!(sums, total);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use aoc_common::combinator::{lines, parse_all, preceded, spaces, tag, uint, word, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::params;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+params! {
+    /// Cubes of each colour in the bag of part 1.
+    pub struct Bag {
+        pub red: u32 = 12,
+        pub green: u32 = 13,
+        pub blue: u32 = 14,
+    }
+}
+
+#[derive(Debug)]
+enum Color {
+    R(u32),
+    G(u32),
+    B(u32),
+}
+
+/// Cubes of a colour, like `3 blue`.
+fn color<'a>() -> impl Parser<'a, Color> {
+    let name = word().try_map(|name| match name {
+        "red" => Ok(Color::R as fn(u32) -> Color),
+        "green" => Ok(Color::G),
+        "blue" => Ok(Color::B),
+        _ => Err("`red`, `green` or `blue`".to_string()),
+    });
+    uint()
+        .skip(spaces())
+        .then(name)
+        .map(|(cnt, color)| color(cnt))
+}
+
+#[derive(Debug)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<Vec<Color>>,
+}
+
+/// A game like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+fn game<'a>() -> impl Parser<'a, Game> {
+    let round = color().sep_by(tag(", "));
+    preceded(tag("Game "), uint())
+        .skip(tag(": "))
+        .then(round.sep_by(tag("; ")))
+        .map(|(id, rounds)| Game { id, rounds })
+}
+
+fn p1(games: &[Game], bag: &Bag) -> u32 {
+    let sum = games
+        .iter()
+        .filter(|game| {
+            game.rounds.iter().all(|round| {
+                round.iter().all(|color| match color {
+                    Color::R(cnt) if *cnt <= bag.red => true,
+                    Color::G(cnt) if *cnt <= bag.green => true,
+                    Color::B(cnt) if *cnt <= bag.blue => true,
+                    _ => false,
+                })
+            })
+        })
+        .map(|game| game.id)
+        .sum();
+    sum
+}
+
+fn p2(games: &[Game]) -> u32 {
+    let mut sum = 0;
+
+    for game in games {
+        let (mut max_r, mut max_b, mut max_g) = (0, 0, 0);
+        for round in &game.rounds {
+            for c in round {
+                match c {
+                    Color::R(cnt) => max_r = max_r.max(*cnt),
+                    Color::G(cnt) => max_g = max_g.max(*cnt),
+                    Color::B(cnt) => max_b = max_b.max(*cnt),
+                }
+            }
+        }
+        sum += max_r * max_g * max_b;
+    }
+    sum
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Model<'a> = Vec<Game>;
+    type Params = Bag;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(lines(game()), input)
+    }
+
+    fn part1(games: &Self::Model<'_>, bag: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(games, bag).into())
+    }
+
+    fn part2(games: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(games).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn part1() {
+        let games = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(p1(&games, &Bag::default()), 8);
+        let bag = Bag {
+            red: 20,
+            ..Bag::default()
+        };
+        assert_eq!(p1(&games, &bag), 11);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day02::parse(EXAMPLE).unwrap()), 2286);
+    }
+}
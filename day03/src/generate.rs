@@ -0,0 +1,54 @@
+//! Engine schematics `size` characters wide and high.
+
+use aoc_common::rng::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let mut row = vec![b'.'; size];
+        let mut x = 0;
+        while x < size {
+            if rng.chance(1, 4) {
+                // a number, with room left for a gap before the next one
+                let len = rng.index(1..4).min(size - x);
+                for c in &mut row[x..x + len] {
+                    *c = b'0' + rng.below(10) as u8;
+                }
+                row[x] = b'1' + rng.below(9) as u8;
+                x += len + 1;
+            } else {
+                if rng.chance(1, 8) {
+                    row[x] = if rng.chance(1, 2) {
+                        b'*'
+                    } else {
+                        *rng.pick(SYMBOLS)
+                    };
+                }
+                x += 1;
+            }
+        }
+        s.push_str(std::str::from_utf8(&row).unwrap());
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day03;
+
+    #[test]
+    fn parts_among_numbers() {
+        for (input, answers) in solve_generated::<Day03>(0..20, 40) {
+            let numbers: i128 = input
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|n| n.parse::<i128>().ok())
+                .sum();
+            assert!(answers[0] <= numbers, "{input}");
+        }
+    }
+}
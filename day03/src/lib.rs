@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+
+use aoc_common::combinator::{grid_with, parse_all};
+use aoc_common::error::ParseResult;
+use aoc_common::geom::Pos;
+use aoc_common::grid::Grid;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+#[derive(Debug)]
+pub enum Tile {
+    Empty,
+    Num(i64),
+    Symbol(char),
+}
+
+impl Tile {
+    fn from_char(c: char) -> Tile {
+        match c {
+            '.' => Tile::Empty,
+            x if x.is_ascii_digit() => Tile::Num(x.to_digit(10).unwrap() as i64),
+            x => Tile::Symbol(x),
+        }
+    }
+}
+
+fn p1(tilemap: &Grid<Tile>) -> i64 {
+    let mut sum = 0;
+    for y in 0..tilemap.height() as i64 {
+        let mut sym_found = false;
+        let mut num = 0;
+        for x in 0..=tilemap.width() as i64 {
+            if let Some(Tile::Num(n)) = tilemap.get((x, y)) {
+                num = num * 10 + n;
+                if !sym_found {
+                    sym_found = tilemap
+                        .neighbours8((x, y))
+                        .any(|(_, t)| matches!(t, Tile::Symbol(_)));
+                }
+            } else {
+                if sym_found {
+                    sum += num;
+                }
+                num = 0;
+                sym_found = false;
+            }
+        }
+    }
+
+    sum
+}
+
+fn getnum(tilemap: &Grid<Tile>, p: Pos) -> (Pos, i64) {
+    let mut num = 0;
+    let mut start = p;
+    while let Some(Tile::Num(_)) = tilemap.get(start) {
+        start.0 -= 1;
+    }
+    start.0 += 1;
+
+    let mut c = start;
+    while let Some(Tile::Num(n)) = tilemap.get(c) {
+        num = num * 10 + n;
+        c.0 += 1;
+    }
+    (start, num)
+}
+
+fn p2(tilemap: &Grid<Tile>) -> i64 {
+    let mut sum = 0;
+    for (p, _) in tilemap
+        .iter()
+        .filter(|(_, t)| matches!(t, Tile::Symbol('*')))
+    {
+        let nums: HashSet<_> = tilemap
+            .neighbours8(p)
+            .filter(|(_, t)| matches!(t, Tile::Num(_)))
+            .map(|(p, _)| getnum(tilemap, p))
+            .collect();
+
+        if nums.len() == 2 {
+            sum += nums.iter().fold(1, |m, n| m * n.1);
+        }
+    }
+    sum
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Model<'a> = Grid<Tile>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(grid_with(|c| Ok(Tile::from_char(c))), input)
+    }
+
+    fn part1(schematic: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(schematic).into())
+    }
+
+    fn part2(schematic: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(schematic).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day03::parse(EXAMPLE).unwrap()), 4361);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day03::parse(EXAMPLE).unwrap()), 467835);
+    }
+}
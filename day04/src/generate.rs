@@ -0,0 +1,72 @@
+//! Piles of `size` scratchcards.
+
+use aoc_common::rng::Rng;
+
+const WINNING: usize = 5;
+const HAVE: usize = 8;
+
+/// `n` different numbers between 1 and 99, none of them in `not`.
+fn numbers(rng: &mut Rng, n: usize, not: &[u32]) -> Vec<u32> {
+    let mut nums = vec![];
+    while nums.len() < n {
+        let x = rng.range(1..100) as u32;
+        if !nums.contains(&x) && !not.contains(&x) {
+            nums.push(x);
+        }
+    }
+    nums
+}
+
+fn join(nums: &[u32]) -> String {
+    let nums: Vec<_> = nums.iter().map(|n| format!("{n:>2}")).collect();
+    nums.join(" ")
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    // copies won of each card, kept low enough for the total to fit a u32
+    let mut copies = vec![1; size];
+    let mut total = size;
+
+    let mut s = String::new();
+    for i in 1..=size {
+        let winning = numbers(rng, WINNING, &[]);
+        // cards never win copies of cards past the end of the pile
+        let won = copies[i - 1];
+        let mut matches = rng.index(0..WINNING + 1).min(size - i);
+        while total + matches * won > 1_000_000 {
+            matches -= 1;
+        }
+        for c in &mut copies[i..i + matches] {
+            *c += won;
+        }
+        total += matches * won;
+
+        let mut have = winning[..matches].to_vec();
+        have.extend(numbers(rng, HAVE - matches, &winning));
+        rng.shuffle(&mut have);
+        s.push_str(&format!(
+            "Card {i:>3}: {} | {}\n",
+            join(&winning),
+            join(&have)
+        ));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day04;
+
+    /// Cards are worth at most 16 points with 5 winning numbers, and the
+    /// copies won are kept under a million.
+    #[test]
+    fn copies_fit() {
+        for (input, answers) in solve_generated::<Day04>(0..20, 200) {
+            let cards = input.lines().count() as i128;
+            assert!(answers[0] <= 16 * cards, "{input}");
+            assert!((cards..=1_000_000).contains(&answers[1]), "{input}");
+        }
+    }
+}
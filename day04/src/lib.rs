@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use aoc_common::combinator::{lines, parse_all, preceded, spaces, tag, uint, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+#[derive(Debug)]
+pub struct Card {
+    nums: HashSet<u32>,
+}
+
+/// Numbers each after some spaces, like ` 83 86  6 31`.
+fn card<'a>() -> impl Parser<'a, Card> {
+    preceded(spaces(), uint()).many1().map(|nums| Card {
+        nums: nums.into_iter().collect(),
+    })
+}
+
+fn p1(pairs: &[(Card, Card)]) -> u32 {
+    let mut sum = 0;
+    for (winner, card) in pairs {
+        let winners = winner.nums.intersection(&card.nums).count();
+        if winners > 0 {
+            sum += 2_u32.pow(winners as u32 - 1);
+        }
+    }
+    sum
+}
+
+fn p2(pairs: &[(Card, Card)]) -> u32 {
+    let mut done = 0;
+    let mut cards = vec![1; pairs.len()];
+
+    for i in 0..cards.len() {
+        done += cards[i];
+        let (winner, card) = &pairs[i];
+        let winners = winner.nums.intersection(&card.nums).count();
+        // a pile cut short has no copies to win past its end
+        let last = (i + winners).min(pairs.len() - 1);
+        for n in (i + 1)..=last {
+            cards[n] += cards[i];
+        }
+    }
+
+    done
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Model<'a> = Vec<(Card, Card)>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let id = tag("Card")
+            .then(spaces())
+            .then(uint::<u32>())
+            .then(tag(":"));
+        let pair = preceded(id, card()).skip(tag(" |")).then(card());
+        parse_all(lines(pair), input)
+    }
+
+    fn part1(pairs: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(pairs).into())
+    }
+
+    fn part2(pairs: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(pairs).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day04::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day04::parse(EXAMPLE).unwrap()), 30);
+    }
+
+    #[test]
+    fn short_pile() {
+        // the first card wins copies of the next four, only one is left
+        let two = &EXAMPLE[..EXAMPLE.find("Card 3").unwrap()];
+        assert_eq!(p2(&Day04::parse(two).unwrap()), 3);
+    }
+}
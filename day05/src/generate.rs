@@ -0,0 +1,45 @@
+//! Almanacs with about `size` rules in each of the seven maps.
+
+use aoc_common::rng::Rng;
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let space = 1000 * (size as i64 + 1);
+
+    let seeds: Vec<_> = (0..1 + size / 4)
+        .flat_map(|_| {
+            let start = rng.range(0..space);
+            [start, rng.range(1..space / 10 + 2)]
+        })
+        .map(|n| n.to_string())
+        .collect();
+    let mut s = format!("seeds: {}\n", seeds.join(" "));
+
+    for w in CATEGORIES.windows(2) {
+        s.push_str(&format!("\n{}-to-{} map:\n", w[0], w[1]));
+
+        // the sources of the rules don't overlap
+        let mut cuts: Vec<_> = (0..2 * size).map(|_| rng.range(0..space)).collect();
+        cuts.sort();
+        cuts.dedup();
+        let mut rules: Vec<_> = cuts
+            .chunks_exact(2)
+            .map(|c| (rng.range(0..space), c[0], c[1] - c[0]))
+            .collect();
+        rng.shuffle(&mut rules);
+        for (dst, src, len) in rules {
+            s.push_str(&format!("{dst} {src} {len}\n"));
+        }
+    }
+    s
+}
@@ -0,0 +1,247 @@
+use aoc_common::combinator::{
+    blank_line, line, lines, newline, parse_all, preceded, sections, spaces, tag, take_while1,
+    uint, Parser,
+};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::interval::{Interval, RangeSet};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+#[cfg(test)]
+mod reference;
+
+#[derive(Debug)]
+pub struct MapRule {
+    dst: u64,
+    src: u64,
+    range: u64,
+}
+
+/// A rule like `50 98 2`.
+fn map_rule<'a>() -> impl Parser<'a, MapRule> {
+    uint()
+        .skip(spaces())
+        .then(uint())
+        .skip(spaces())
+        .then(uint())
+        .try_map(|((dst, src), range): ((u64, u64), u64)| {
+            if dst.checked_add(range).is_some() && src.checked_add(range).is_some() {
+                Ok(MapRule { dst, src, range })
+            } else {
+                Err("a rule with ranges that fit in a u64".to_string())
+            }
+        })
+}
+
+impl MapRule {
+    fn src(&self) -> Interval<u64> {
+        Interval::from_len(self.src, self.range)
+    }
+
+    fn get(&self, n: u64) -> Option<u64> {
+        if self.src().contains(n) {
+            Some(self.dst + (n - self.src))
+        } else {
+            None
+        }
+    }
+
+    fn conv(&self, n: Interval<u64>) -> Interval<u64> {
+        n.shift(self.src, self.dst)
+            .expect("checked to fit in a u64")
+    }
+}
+
+/// A line like `seeds: 79 14 55 13`, with pairs of numbers.
+fn seeds<'a>() -> impl Parser<'a, (&'a str, Vec<u64>)> {
+    let nums = uint().sep_by(spaces()).try_map(|seeds: Vec<u64>| {
+        if seeds.len().is_multiple_of(2) {
+            Ok(seeds)
+        } else {
+            Err("an even number of seeds".to_string())
+        }
+    });
+    line(preceded(tag("seeds:").then(spaces()), nums.with_text()))
+}
+
+/// A `seed-to-soil map:` line followed by its rules.
+fn map_section<'a>() -> impl Parser<'a, Vec<MapRule>> {
+    let name = take_while1("a map name", |c| c.is_ascii_alphabetic() || c == '-');
+    preceded(
+        line(name.then(tag(" map:"))).then(newline()),
+        lines(map_rule()),
+    )
+}
+
+pub struct Almanac<'a> {
+    seeds: Vec<u64>,
+    /// The numbers of the seeds line, for errors about the ranges.
+    seed_text: &'a str,
+    maps: Vec<Vec<MapRule>>,
+}
+
+fn p1(almanac: &Almanac) -> u64 {
+    let Almanac { seeds, maps, .. } = almanac;
+
+    seeds
+        .iter()
+        .map(|s| {
+            let mut mapped = *s;
+            for m in maps {
+                for r in m {
+                    if let Some(v) = r.get(mapped) {
+                        mapped = v;
+                        break;
+                    }
+                }
+            }
+            mapped
+        })
+        .min()
+        .unwrap()
+}
+
+fn p2(almanac: &Almanac) -> u64 {
+    let mut cur: RangeSet<u64> = almanac
+        .seeds
+        .chunks(2)
+        .map(|c| Interval::from_len(c[0], c[1]))
+        .collect();
+    for m in &almanac.maps {
+        // values no rule maps stay as they are
+        let mut next = RangeSet::new();
+        for r in m {
+            let src = RangeSet::from(r.src());
+            for iv in cur.intersection(&src).iter() {
+                next.insert(r.conv(*iv));
+            }
+            cur = cur.difference(&src);
+        }
+        cur = cur.union(&next);
+    }
+
+    cur.min().expect("checked to have seeds")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Model<'a> = Almanac<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let almanac = seeds()
+            .skip(blank_line())
+            .then(sections(map_section()))
+            .map(|((seed_text, seeds), maps)| Almanac {
+                seeds,
+                seed_text,
+                maps,
+            });
+        parse_all(almanac, input)
+    }
+
+    /// Part 2 reads the seeds as ranges, which have to hold some seeds and
+    /// end where a `u64` can tell.
+    fn check(almanac: &Self::Model<'_>, part: u8, _params: &Self::Params) -> ParseResult<()> {
+        if part == 1 {
+            return Ok(());
+        }
+        let lens = almanac.seed_text.split_whitespace().skip(1).step_by(2);
+        for (c, len) in almanac.seeds.chunks(2).zip(lens) {
+            if c[0].checked_add(c[1]).is_none() {
+                return Err(ParseError::new("a seed range that fits in a u64", len));
+            }
+        }
+        if almanac.seeds.chunks(2).all(|c| c[1] == 0) {
+            return Err(ParseError::new(
+                "a seed range of at least one seed",
+                almanac.seed_text,
+            ));
+        }
+        Ok(())
+    }
+
+    fn part1(almanac: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(almanac).into())
+    }
+
+    fn part2(almanac: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(almanac).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day05::parse(EXAMPLE).unwrap()), 35);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day05::parse(EXAMPLE).unwrap()), 46);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let input = "seeds: 1 2\n\na-to-b map:\n18446744073709551610 0 10\n";
+        let Err(err) = Day05::parse(input) else {
+            panic!("parsed");
+        };
+        assert_eq!(err.expected, "a rule with ranges that fit in a u64");
+
+        let almanac = Day05::parse("seeds: 1 0\n\na-to-b map:\n5 0 10\n").unwrap();
+        assert_eq!(p1(&almanac), 5);
+        let err = Day05::check(&almanac, 2, &()).unwrap_err();
+        assert_eq!(err.expected, "a seed range of at least one seed");
+
+        let input = "seeds: 3 1 18446744073709551615 2\n\na-to-b map:\n5 0 10\n";
+        let err = Day05::check(&Day05::parse(input).unwrap(), 2, &()).unwrap_err();
+        assert_eq!(err.locate(input).column, Some(33));
+    }
+}
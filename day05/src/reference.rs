@@ -0,0 +1,46 @@
+//! Slow but plain solutions to check the range splitting against: every
+//! seed is looked up through the maps one by one.
+
+use aoc_common::rng::Rng;
+use aoc_common::solution::Solution;
+
+use crate::{generate, Almanac, Day05};
+
+fn location(almanac: &Almanac, seed: u64) -> u64 {
+    let mut n = seed;
+    for map in &almanac.maps {
+        if let Some(r) = map.iter().find(|r| r.src <= n && n < r.src + r.range) {
+            n = r.dst + (n - r.src);
+        }
+    }
+    n
+}
+
+fn p1(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .iter()
+        .map(|&s| location(almanac, s))
+        .min()
+        .unwrap()
+}
+
+fn p2(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .chunks(2)
+        .flat_map(|c| c[0]..c[0] + c[1])
+        .map(|s| location(almanac, s))
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn matches_fast() {
+    for seed in 0..20 {
+        let input = generate::input(&mut Rng::new(seed), 8);
+        let almanac = Day05::parse(&input).unwrap();
+        assert_eq!(crate::p1(&almanac), p1(&almanac), "seed {seed}");
+        assert_eq!(crate::p2(&almanac), p2(&almanac), "seed {seed}");
+    }
+}
@@ -0,0 +1,36 @@
+//! Sheets of `size` races, at most 4 like the puzzle's. The times have two
+//! digits, so the single race of part 2 lasts a number of `2 * size`
+//! digits.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut times = String::from("Time:    ");
+    let mut dists = String::from("Distance:");
+    for _ in 0..size.clamp(1, 4) {
+        let t = rng.range(10..100);
+        // the record can always be beaten by holding the button half the race
+        let best = (t / 2) * (t - t / 2);
+        let d = rng.range(0..best);
+        let width = d.to_string().len().max(t.to_string().len()) + 1;
+        times.push_str(&format!(" {t:>width$}"));
+        dists.push_str(&format!(" {d:>width$}"));
+    }
+    format!("{times}\n{dists}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day06;
+
+    #[test]
+    fn records_beaten() {
+        for size in 1..5 {
+            for (input, answers) in solve_generated::<Day06>(0..10, size) {
+                assert!(answers.iter().all(|&ways| ways > 0), "{input}");
+            }
+        }
+    }
+}
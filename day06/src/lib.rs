@@ -0,0 +1,164 @@
+use aoc_common::combinator::{
+    line, newline, parse_all, preceded, spaces, tag, uint, Parsed, Parser,
+};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+pub struct Races<'a> {
+    times: Vec<u64>,
+    dists: Vec<u64>,
+    /// The numbers of each line, for errors about joining them.
+    texts: [&'a str; 2],
+}
+
+/// Ways to beat the distance `d` in a race of `t` milliseconds. Holding the
+/// button `i` milliseconds goes `(t - i) * i`, which grows up to `t / 2` and
+/// then shrinks the same way, so the winning holds go from the first one
+/// beating `d` to its mirror.
+fn wins(t: u64, d: u64) -> u64 {
+    let beats = |i: u64| u128::from(t - i) * u128::from(i) > u128::from(d);
+    let (mut lo, mut hi) = (0, t / 2);
+    if !beats(hi) {
+        return 0;
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if beats(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    t - 2 * lo + 1
+}
+
+/// Reads the numbers of one line again with the spaces between them removed,
+/// `None` if that doesn't fit in a `u64`.
+fn join(nums: &[u64]) -> Option<u64> {
+    nums.iter()
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+fn p1(races: &Races) -> u64 {
+    races
+        .times
+        .iter()
+        .zip(races.dists.iter())
+        .map(|(&t, &d)| wins(t, d))
+        .product()
+}
+
+fn p2(races: &Races) -> u64 {
+    let joined = |nums| join(nums).expect("checked to fit in a u64");
+    wins(joined(&races.times), joined(&races.dists))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Model<'a> = Races<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let nums = |name| {
+            line(preceded(
+                tag(name).then(tag(":")).then(spaces()),
+                uint().sep_by(spaces()).with_text(),
+            ))
+        };
+        let races = |s| -> Parsed<Races> {
+            let ((time_text, times), rest) = nums("Time").skip(newline()).parse(s)?;
+            let n = times.len();
+            let ((dist_text, dists), rest) = nums("Distance")
+                .try_map(|(text, dists): (&str, Vec<u64>)| {
+                    if dists.len() == n {
+                        Ok((text, dists))
+                    } else {
+                        Err(format!("{n} distances"))
+                    }
+                })
+                .parse(rest)?;
+            let texts = [time_text, dist_text];
+            Ok((
+                Races {
+                    times,
+                    dists,
+                    texts,
+                },
+                rest,
+            ))
+        };
+        parse_all(races, input)
+    }
+
+    /// Part 2 joins the numbers of each line into one, which has to fit.
+    fn check(races: &Self::Model<'_>, part: u8, _params: &Self::Params) -> ParseResult<()> {
+        for (nums, text) in [&races.times, &races.dists].into_iter().zip(races.texts) {
+            if part == 2 && join(nums).is_none() {
+                return Err(ParseError::new(
+                    "numbers that fit in a u64 once joined",
+                    text,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn part1(races: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(races).into())
+    }
+
+    fn part2(races: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(races).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn ways_to_win() {
+        assert_eq!(wins(7, 9), 4);
+        assert_eq!(wins(15, 40), 8);
+        assert_eq!(wins(30, 200), 9);
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day06::parse(EXAMPLE).unwrap()), 288);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day06::parse(EXAMPLE).unwrap()), 71503);
+    }
+
+    #[test]
+    fn long_races() {
+        assert_eq!(wins(u64::MAX, u64::MAX), u64::MAX - 3);
+        assert_eq!(wins(10, 25), 0);
+
+        let input = "Time: 10000000000 10000000000\nDistance: 1 2\n";
+        let races = Day06::parse(input).unwrap();
+        assert!(Day06::check(&races, 1, &()).is_ok());
+        let err = Day06::check(&races, 2, &()).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(1), Some(7)));
+    }
+}
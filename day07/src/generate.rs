@@ -0,0 +1,42 @@
+//! Lists of `size` different hands with their bids.
+
+use std::collections::HashSet;
+
+use aoc_common::rng::Rng;
+
+use crate::CARDS;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let cards: Vec<_> = CARDS.chars().collect();
+    let mut seen = HashSet::new();
+    let mut s = String::new();
+    while seen.len() < size {
+        // few kinds of cards make pairs and full houses common
+        let kinds = rng.index(2..cards.len() + 1);
+        let hand: String = (0..5).map(|_| cards[rng.index(0..kinds)]).collect();
+        if seen.insert(hand.clone()) {
+            s.push_str(&format!("{hand} {}\n", rng.range(1..1001)));
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day07;
+
+    /// Ranks go from 1 to the number of hands, so the winnings are between
+    /// those of the bids ranked from highest to lowest and the other way.
+    #[test]
+    fn winnings_between_orders() {
+        for (input, answers) in solve_generated::<Day07>(0..20, 100) {
+            let mut bids: Vec<i128> = input.lines().map(|l| l[6..].parse().unwrap()).collect();
+            bids.sort();
+            let least = bids.iter().rev().zip(1..).map(|(b, r)| b * r).sum();
+            let most = bids.iter().zip(1..).map(|(b, r)| b * r).sum();
+            assert!(answers.iter().all(|w| (least..=most).contains(w)));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use aoc_common::combinator::{lines, parse_all, spaces, take_while1, uint, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+fn score(s: &str, joker: bool) -> u64 {
+    let mut card_lut = HashMap::new();
+    for c in s.chars() {
+        let cnt = card_lut.entry(c).or_insert(0_usize);
+        *cnt += 1;
+    }
+
+    if joker {
+        if let Some(&j) = card_lut.get(&'J') {
+            if j != 5 {
+                card_lut.remove(&'J');
+                let m = card_lut.values_mut().max().unwrap();
+                *m += j
+            }
+        }
+    }
+
+    match (card_lut.len(), card_lut.values().max().unwrap()) {
+        (5, _) => 0,
+        (4, _) => 1,
+        (3, 2) => 2,
+        (3, 3) => 3,
+        (2, 3) => 4,
+        (2, 4) => 5,
+        (1, _) => 6,
+        _ => unreachable!(),
+    }
+}
+
+const CARDS: &str = "AKQJT98765432";
+
+fn compare_cards(a: &str, b: &str, joker: bool) -> Ordering {
+    let score_a = score(a, joker);
+    let score_b = score(b, joker);
+
+    let cards = if joker {
+        [
+            'A', 'K', 'Q', 'T', '9', '8', '7', '6', '5', '4', '3', '2', 'J',
+        ]
+    } else {
+        [
+            'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+        ]
+    };
+
+    if score_a != score_b {
+        score_a.cmp(&score_b)
+    } else {
+        let compare = a.chars().zip(b.chars()).find(|(ca, cb)| ca != cb);
+        if let Some((ca, cb)) = compare {
+            let idx_a = cards.iter().position(|&c| ca == c).unwrap();
+            let idx_b = cards.iter().position(|&c| cb == c).unwrap();
+            idx_b.cmp(&idx_a)
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+fn p1(hands: &[(&str, u64)]) -> u64 {
+    let mut list = hands.to_vec();
+
+    list.sort_by(|a, b| compare_cards(a.0, b.0, false));
+    list.iter()
+        .enumerate()
+        .map(|(idx, (_, bid))| (idx as u64 + 1) * bid)
+        .sum()
+}
+
+fn p2(hands: &[(&str, u64)]) -> u64 {
+    let mut list = hands.to_vec();
+
+    list.sort_by(|a, b| compare_cards(a.0, b.0, true));
+    list.iter()
+        .enumerate()
+        .map(|(idx, (_, bid))| (idx as u64 + 1) * bid)
+        .sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Model<'a> = Vec<(&'a str, u64)>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let hand = take_while1("a hand of 5 cards", |c| c != ' ').try_map(|cards: &str| {
+            if cards.chars().count() == 5 && cards.chars().all(|c| CARDS.contains(c)) {
+                Ok(cards)
+            } else {
+                Err("a hand of 5 cards".to_string())
+            }
+        });
+        parse_all(lines(hand.skip(spaces()).then(uint())), input)
+    }
+
+    fn part1(hands: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(hands).into())
+    }
+
+    fn part2(hands: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(hands).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn hand_type() {
+        assert_eq!(score("32T3K", false), 1);
+        assert_eq!(score("KK677", false), 2);
+        assert_eq!(score("KTJJT", false), 2);
+        assert_eq!(score("T55J5", false), 3);
+        assert_eq!(score("AAAAA", false), 6);
+        assert_eq!(score("T55J5", true), 5);
+        assert_eq!(score("KTJJT", true), 5);
+        assert_eq!(score("JJJJJ", true), 6);
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day07::parse(EXAMPLE).unwrap()), 6440);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day07::parse(EXAMPLE).unwrap()), 5905);
+    }
+}
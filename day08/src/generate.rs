@@ -0,0 +1,112 @@
+//! Networks of about `size` nodes.
+//!
+//! As in the real inputs, each ghost walks a ring whose length is a multiple
+//! of the instructions' and stands on a `Z` node once per lap. Some nodes of
+//! the ring fork into two ways meeting again at the next one, so the steps
+//! around the ring don't depend on the instructions. The ghost starts on an
+//! `A` node outside the ring, leading where its `Z` node does, so part 2 has
+//! an answer. The first ghost walks from `AAA` to `ZZZ`.
+
+use std::collections::HashSet;
+
+use aoc_common::rng::Rng;
+
+/// A new node name ending in `last`, or in neither `A` nor `Z` if `None`.
+fn name(rng: &mut Rng, used: &mut HashSet<String>, last: Option<char>) -> String {
+    loop {
+        let mut name: String = (0..3)
+            .map(|_| char::from(b'A' + rng.below(26) as u8))
+            .collect();
+        if let Some(c) = last {
+            name.replace_range(2.., &c.to_string());
+        }
+        if last.is_none() && name.ends_with(['A', 'Z']) {
+            continue;
+        }
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let ghosts = (1 + size / 100).min(6);
+    let mut laps = [2, 3, 5, 7, 11, 13, 17, 19];
+    rng.shuffle(&mut laps);
+    let laps = &laps[..ghosts];
+    // a step is about one and a half nodes
+    let len = (size * 2 / (3 * laps.iter().sum::<usize>())).max(1);
+    let instructions: String = (0..len)
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect();
+
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut nodes = vec![];
+    for (ghost, &lap) in laps.iter().enumerate() {
+        // steps from each node of the ring to the next, one or two
+        let mut steps = vec![];
+        let mut left = len * lap;
+        while left > 0 {
+            let step = if left > 1 && rng.chance(1, 2) { 2 } else { 1 };
+            steps.push(step);
+            left -= step;
+        }
+
+        let ring: Vec<_> = (0..steps.len())
+            .map(|i| match (ghost, i) {
+                (0, 0) => "ZZZ".to_string(),
+                (_, 0) => name(rng, &mut used, Some('Z')),
+                _ => name(rng, &mut used, None),
+            })
+            .collect();
+        let start = if ghost == 0 {
+            "AAA".to_string()
+        } else {
+            name(rng, &mut used, Some('A'))
+        };
+
+        // the start stands in for the `Z` node
+        let n = ring.len();
+        let links = (0..n).map(|i| (&ring[i], &ring[(i + 1) % n], steps[i]));
+        for (from, to, step) in links.chain([(&start, &ring[1 % n], steps[0])]) {
+            if step == 1 {
+                nodes.push(format!("{from} = ({to}, {to})"));
+            } else {
+                let left = name(rng, &mut used, None);
+                let right = name(rng, &mut used, None);
+                nodes.push(format!("{from} = ({left}, {right})"));
+                nodes.push(format!("{left} = ({to}, {to})"));
+                nodes.push(format!("{right} = ({to}, {to})"));
+            }
+        }
+    }
+    rng.shuffle(&mut nodes);
+
+    format!("{instructions}\n\n{}\n", nodes.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day08;
+
+    /// Each ghost's ring takes a prime number of rounds of the instructions,
+    /// a different one for each ghost, and `AAA` is the first ghost's start.
+    #[test]
+    fn prime_laps() {
+        const PRIMES: [i128; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+        for (input, answers) in solve_generated::<Day08>(0..20, 300) {
+            let round = input.lines().next().unwrap().len() as i128;
+            let ghosts = input.lines().filter(|l| l.get(2..4) == Some("A ")).count();
+            let [p1, p2] = answers[..] else {
+                panic!("two parts");
+            };
+            assert_eq!((p1 % round, p2 % round), (0, 0));
+            let laps: Vec<_> = PRIMES.into_iter().filter(|p| p2 / round % p == 0).collect();
+            assert_eq!(laps.iter().product::<i128>(), p2 / round);
+            assert_eq!(laps.len(), ghosts);
+            assert!(laps.contains(&(p1 / round)));
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+
+use aoc_common::combinator::{
+    blank_line, bracketed, line, lines, parse_all, tag, take_while1, word, Parser,
+};
+use aoc_common::cycle;
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::math::crt;
+use aoc_common::parse::end;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+pub struct Network<'a> {
+    instructions: &'a [u8],
+    /// Every node has two edges, to the left then to the right.
+    graph: Graph<&'a str>,
+    /// End of the input, where errors about missing nodes point.
+    end: &'a str,
+}
+
+impl Network<'_> {
+    fn next(&self, id: NodeId, turn: u8) -> NodeId {
+        self.graph.edges(id)[usize::from(turn == b'R')].0
+    }
+}
+
+/// Steps from `AAA` to `ZZZ`, `None` if either is missing or the walk never
+/// gets there.
+fn steps_to_zzz(network: &Network) -> Option<u64> {
+    let mut id = network.graph.id(&"AAA")?;
+    let target = network.graph.id(&"ZZZ")?;
+    // the walk repeats once it's back on a node at the same instruction
+    let limit = network.graph.len() * network.instructions.len();
+    let turns = network.instructions.iter().cycle().take(limit);
+    for (steps, &turn) in turns.enumerate() {
+        id = network.next(id, turn);
+        if id == target {
+            return Some(steps as u64 + 1);
+        }
+    }
+    None
+}
+
+fn p1(network: &Network) -> ParseResult<u64> {
+    steps_to_zzz(network).ok_or_else(|| ParseError::new("a way from `AAA` to `ZZZ`", network.end))
+}
+
+/// Where a ghost stands on `Z` nodes along its walk, which repeats once it's
+/// back on the same node at the same point of the instructions.
+struct Ghost {
+    /// Steps before the walk starts repeating.
+    start: u64,
+    period: u64,
+    /// Steps at which the ghost is on a `Z` node, up to `start + period`.
+    hits: Vec<u64>,
+}
+
+impl Ghost {
+    fn walk(network: &Network, start: NodeId) -> Ghost {
+        let instructions = network.instructions;
+        let (cycle, states) = cycle::hashed((start, 0), |&(id, i)| {
+            (
+                network.next(id, instructions[i]),
+                (i + 1) % instructions.len(),
+            )
+        });
+        let hits = states
+            .iter()
+            .enumerate()
+            .filter(|(_, (id, _))| network.graph.label(*id).ends_with('Z'))
+            .map(|(t, _)| t as u64)
+            .collect();
+
+        Ghost {
+            start: cycle.tail as u64,
+            period: cycle.period as u64,
+            hits,
+        }
+    }
+
+    fn on_z(&self, t: u64) -> bool {
+        let t = if t < self.start {
+            t
+        } else {
+            self.start + (t - self.start) % self.period
+        };
+        self.hits.contains(&t)
+    }
+
+    fn cycle_hits(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hits.iter().copied().filter(|&t| t >= self.start)
+    }
+}
+
+/// Nodes the ghosts start on.
+fn starts<'a>(network: &'a Network) -> impl Iterator<Item = NodeId> + 'a {
+    (0..network.graph.len()).filter(|&id| network.graph.label(id).ends_with('A'))
+}
+
+/// Steps until every ghost stands on a `Z` node, `None` if they never do.
+fn steps_to_all_z(network: &Network) -> Option<u64> {
+    let ghosts: Vec<_> = starts(network).map(|id| Ghost::walk(network, id)).collect();
+
+    // before every walk repeats there is nothing to do but check each step
+    let latest = ghosts.iter().map(|g| g.start).max().unwrap_or(0);
+    if let Some(t) = (0..latest).find(|&t| ghosts.iter().all(|g| g.on_z(t))) {
+        return Some(t);
+    }
+
+    // after it, add the ghosts one at a time to the times when all the ones
+    // so far are on `Z` nodes, kept as residues modulo the lcm of their
+    // periods, of which there can't be more than the lcm
+    let mut times = HashSet::from([(0, 1)]);
+    for g in &ghosts {
+        times = times
+            .iter()
+            .flat_map(|&time| {
+                g.cycle_hits()
+                    .filter_map(move |t| crt(&[time, (t as i128, g.period as i128)]))
+            })
+            .collect();
+    }
+
+    let latest = latest as i128;
+    times
+        .into_iter()
+        .filter_map(|(x, l)| {
+            let t = if x < latest {
+                x + (latest - x + l - 1) / l * l
+            } else {
+                x
+            };
+            u64::try_from(t).ok()
+        })
+        .min()
+}
+
+fn p2(network: &Network) -> ParseResult<u64> {
+    steps_to_all_z(network)
+        .ok_or_else(|| ParseError::new("ghosts all standing on `Z` nodes at once", network.end))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Model<'a> = Network<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let turns = line(take_while1("`L` or `R`", |c| c == 'L' || c == 'R'));
+        let node =
+            word()
+                .skip(tag(" = "))
+                .then(bracketed("(", word().skip(tag(", ")).then(word()), ")"));
+        let (instructions, nodes) = parse_all(turns.skip(blank_line()).then(lines(node)), input)?;
+
+        let mut graph = Graph::directed();
+        for (pos, (l, r)) in nodes {
+            let id = graph.node(pos);
+            if !graph.edges(id).is_empty() {
+                return Err(ParseError::new("a new node", pos));
+            }
+            graph.add_edge(pos, l);
+            graph.add_edge(pos, r);
+        }
+
+        // nodes only seen as destinations have no edges
+        if let Some(id) = (0..graph.len()).find(|&id| graph.edges(id).is_empty()) {
+            return Err(ParseError::new("a known node", graph.label(id)));
+        }
+
+        Ok(Network {
+            instructions: instructions.as_bytes(),
+            graph,
+            end: end(input),
+        })
+    }
+
+    /// The examples of each part lack the nodes the other part starts from.
+    fn check(network: &Self::Model<'_>, part: u8, _params: &Self::Params) -> ParseResult<()> {
+        let missing = |expected: &str| Err(ParseError::new(expected, network.end));
+        if part == 1 {
+            for label in ["AAA", "ZZZ"] {
+                if network.graph.id(&label).is_none() {
+                    return missing(&format!("a node `{label}`"));
+                }
+            }
+        } else if starts(network).next().is_none() {
+            return missing("a node ending in `A`");
+        }
+        Ok(())
+    }
+
+    fn part1(network: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(network)?.into())
+    }
+
+    fn part2(network: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(network)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE3: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day08::parse(EXAMPLE1).unwrap()), Ok(2));
+        assert_eq!(p1(&Day08::parse(EXAMPLE2).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day08::parse(EXAMPLE3).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let network = Day08::parse(EXAMPLE3).unwrap();
+        let err = Day08::check(&network, 1, &()).unwrap_err();
+        assert_eq!(err.expected, "a node `AAA`");
+        assert!(Day08::check(&network, 2, &()).is_ok());
+
+        let network = Day08::parse(EXAMPLE1).unwrap();
+        assert!(Day08::check(&network, 1, &()).is_ok());
+        // `AAA` is the ghost and `ZZZ` its `Z` node
+        assert!(Day08::check(&network, 2, &()).is_ok());
+
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        let network = Day08::parse(input).unwrap();
+        assert!(Day08::check(&network, 1, &()).is_ok());
+        assert_eq!(
+            p1(&network).unwrap_err().locate(input).to_string(),
+            "line 6, column 1, expected a way from `AAA` to `ZZZ`, found end of input"
+        );
+        let err = p2(&network).unwrap_err();
+        assert_eq!(err.expected, "ghosts all standing on `Z` nodes at once");
+    }
+
+    #[test]
+    fn many_z_nodes() {
+        // six ghosts each going round 30 `Z` nodes, which are 30^6
+        // combinations of one `Z` node per ghost
+        let mut input = String::from("L\n\n");
+        for g in "BCDEFG".chars() {
+            input.push_str(&format!("{g}A = ({g}0Z, {g}0Z)\n"));
+            for i in 0..30 {
+                let next = (i + 1) % 30;
+                input.push_str(&format!("{g}{i}Z = ({g}{next}Z, {g}{next}Z)\n"));
+            }
+        }
+        assert_eq!(p2(&Day08::parse(&input).unwrap()), Ok(1));
+    }
+}
@@ -0,0 +1,42 @@
+//! Reports of `size` histories, each of them the values of a polynomial of
+//! low degree with small coefficients.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let coefs: Vec<_> = (0..rng.index(1..6)).map(|_| rng.range(-4..5)).collect();
+        let start = rng.range(-5..6);
+        let values: Vec<_> = (start..start + 21)
+            .map(|x| coefs.iter().rev().fold(0, |acc, c| acc * x + c).to_string())
+            .collect();
+        s.push_str(&values.join(" "));
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::rng::Rng;
+    use aoc_common::solution::answers;
+
+    use crate::Day09;
+
+    /// Each history is the values of a polynomial, so the ones around any
+    /// stretch of it are the ones next to it.
+    #[test]
+    fn polynomial_histories() {
+        for seed in 0..20 {
+            for line in super::input(&mut Rng::new(seed), 10).lines() {
+                let values: Vec<_> = line.split(' ').collect();
+                let n = values.len();
+                let next = answers::<Day09>(&values[..n - 1].join(" "))[0];
+                assert_eq!(next.to_string(), values[n - 1], "{line}");
+                let prev = answers::<Day09>(&values[1..].join(" "))[1];
+                assert_eq!(prev.to_string(), values[0], "{line}");
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use aoc_common::combinator::{int, lines, parse_all, spaces, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+fn p1(lines: &[Vec<i64>]) -> i64 {
+    let mut sum = 0;
+    for l in lines {
+        let mut stack = vec![l.clone()];
+        loop {
+            let next: Vec<_> = stack
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .collect();
+            if next.iter().all(|v| *v == 0) {
+                sum += stack.iter().map(|l| l.last().unwrap()).sum::<i64>();
+                break;
+            }
+            stack.push(next);
+        }
+    }
+    sum
+}
+
+fn p2(lines: &[Vec<i64>]) -> i64 {
+    let mut sum = 0;
+    for l in lines {
+        let mut stack = vec![l.clone()];
+        loop {
+            let next: Vec<_> = stack
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .collect();
+            if next.iter().all(|v| *v == 0) {
+                sum += stack
+                    .iter()
+                    .rev()
+                    .map(|l| l.first().unwrap())
+                    .fold(0, |acc, f| f - acc);
+                break;
+            }
+            stack.push(next);
+        }
+    }
+    sum
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Model<'a> = Vec<Vec<i64>>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(lines(int().sep_by(spaces())), input)
+    }
+
+    fn part1(lines: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(lines).into())
+    }
+
+    fn part2(lines: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(lines).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day09::parse(EXAMPLE).unwrap()), 114);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day09::parse(EXAMPLE).unwrap()), 2);
+    }
+}
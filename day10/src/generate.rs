@@ -0,0 +1,104 @@
+//! Fields `size` tiles wide and high with one loop through the start among
+//! stray pipes.
+//!
+//! The loop is the [`outline`] of a random tree scaled up twice, which never
+//! touches itself and has a tile inside for every cell of the tree.
+
+use aoc_common::generate::outline;
+use aoc_common::geom::{add, scale, Dir, Pos};
+use aoc_common::rng::Rng;
+
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+fn pipe(exits: &[Dir]) -> char {
+    match exits {
+        [Dir::N, Dir::S] => '|',
+        [Dir::W, Dir::E] => '-',
+        [Dir::N, Dir::E] => 'L',
+        [Dir::N, Dir::W] => 'J',
+        [Dir::S, Dir::W] => '7',
+        [Dir::S, Dir::E] => 'F',
+        _ => unreachable!("the outline has two exits at every corner"),
+    }
+}
+
+/// Direction of the step from `a` to the next corner `b`.
+fn towards(a: Pos, b: Pos) -> Dir {
+    Dir::ALL.into_iter().find(|d| d.step(a) == b).unwrap()
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(4) as i64;
+    let n = (size / 4).max(1);
+    let small = outline(rng, n);
+    let next = small.iter().cycle().skip(1);
+    let corners: Vec<_> = small
+        .iter()
+        .zip(next)
+        .flat_map(|(&a, &b)| [scale(a, 2), add(a, b)])
+        .collect();
+
+    let mut grid = vec![vec!['.'; size as usize]; size as usize];
+    for row in &mut grid {
+        for t in row.iter_mut() {
+            if rng.chance(1, 2) {
+                *t = *rng.pick(&PIPES);
+            }
+        }
+    }
+
+    // the scaled corners are in `0..4 * n - 1` both ways
+    let offset = (
+        rng.range(0..size - 4 * n + 2),
+        rng.range(0..size - 4 * n + 2),
+    );
+    let mut outline = vec![];
+    for (i, &c) in corners.iter().enumerate() {
+        let prev = corners[(i + corners.len() - 1) % corners.len()];
+        let next = corners[(i + 1) % corners.len()];
+        let mut exits = [towards(c, prev), towards(c, next)];
+        exits.sort();
+        let (gx, gy) = add(c, offset);
+        grid[gy as usize][gx as usize] = pipe(&exits);
+        outline.push((gx, gy));
+    }
+
+    // no stray pipe may lead into the start
+    let start = *rng.pick(&outline);
+    for d in Dir::ALL {
+        let (x, y) = d.step(start);
+        let Some(t) = grid.get_mut(y as usize).and_then(|r| r.get_mut(x as usize)) else {
+            continue;
+        };
+        let back = match d.opposite() {
+            Dir::N => "|LJ",
+            Dir::S => "|7F",
+            Dir::W => "-J7",
+            Dir::E => "-LF",
+        };
+        if back.contains(*t) && !outline.contains(&(x, y)) {
+            *t = '.';
+        }
+    }
+    grid[start.1 as usize][start.0 as usize] = 'S';
+
+    grid.iter()
+        .flat_map(|r| r.iter().chain(['\n'].iter()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day10;
+
+    /// The loop goes around a tree of `c` cells scaled up twice, so it's
+    /// `4c + 4` tiles long with `2c - 1` inside.
+    #[test]
+    fn tree_loops() {
+        for (input, answers) in solve_generated::<Day10>(0..20, 40) {
+            assert_eq!(answers[1], answers[0] - 3, "{input}");
+        }
+    }
+}
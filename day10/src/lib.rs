@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use aoc_common::combinator::{grid, parse_all};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{add, neighbours4, Dir, Pos, NLIST};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{char_at, end};
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+#[derive(Debug)]
+pub struct Field {
+    tilemap: Grid<char>,
+    start: Pos,
+}
+
+fn neighbors_kv(tilemap: &Grid<char>, p: Pos) -> impl Iterator<Item = (Pos, char)> + '_ {
+    NLIST
+        .iter()
+        .filter_map(move |n| tilemap.get(add(p, *n)).map(|c| (*n, *c)))
+}
+
+fn reachable(n: Pos, c: char) -> bool {
+    matches!(
+        (n, c),
+        ((-1, 0), '-' | 'F' | 'L')
+            | ((1, 0), '-' | '7' | 'J')
+            | ((0, -1), '|' | 'F' | '7')
+            | ((0, 1), '|' | 'J' | 'L')
+    )
+}
+
+fn reachables(tilemap: &Grid<char>, p: Pos) -> Vec<Pos> {
+    neighbors_kv(tilemap, p)
+        .filter(|(k, v)| reachable(*k, *v))
+        .map(|(k, _)| k)
+        .collect()
+}
+
+fn get_start_type(map: &Grid<char>, p: Pos) -> Option<char> {
+    let mut n = reachables(map, p);
+    n.sort();
+    match n[..] {
+        [(-1, 0), (1, 0)] => Some('-'),
+        [(0, -1), (0, 1)] => Some('|'),
+        [(0, 1), (1, 0)] => Some('F'),
+        [(0, -1), (1, 0)] => Some('L'),
+        [(-1, 0), (0, -1)] => Some('J'),
+        [(-1, 0), (0, 1)] => Some('7'),
+        _ => None,
+    }
+}
+
+/// Exits of the start tile in both directions.
+fn start_dirs(field: &Field) -> (Dir, Dir) {
+    match field.tilemap[field.start] {
+        '|' => (Dir::N, Dir::S),
+        '-' => (Dir::E, Dir::W),
+        'L' => (Dir::N, Dir::E),
+        'J' => (Dir::N, Dir::W),
+        '7' => (Dir::S, Dir::W),
+        'F' => (Dir::S, Dir::E),
+        _ => unreachable!(),
+    }
+}
+
+/// Tiles of the loop through the start, in order from it.
+fn pipe_loop(field: &Field, dir: Dir) -> Vec<Pos> {
+    let Field { tilemap, start } = field;
+    let mut pipe = vec![*start];
+    let (mut cur, mut d) = mv(dir, tilemap, *start);
+    while cur != *start {
+        pipe.push(cur);
+        (cur, d) = mv(d, tilemap, cur);
+    }
+    pipe
+}
+
+fn p1(field: &Field) -> i64 {
+    pipe_loop(field, start_dirs(field).0).len() as i64 / 2
+}
+
+fn mv(dir: Dir, map: &Grid<char>, p: Pos) -> (Pos, Dir) {
+    follow(dir, map, p).expect("the loop is checked to close when parsing")
+}
+
+/// The next tile going `dir` from `p` and the way its pipe leads on, `None`
+/// if the pipe there doesn't connect.
+fn follow(dir: Dir, map: &Grid<char>, p: Pos) -> Option<(Pos, Dir)> {
+    let next = dir.step(p);
+
+    let dir = match (dir, *map.get(next)?) {
+        (Dir::N, '|') => Dir::N,
+        (Dir::N, 'F') => Dir::E,
+        (Dir::N, '7') => Dir::W,
+
+        (Dir::S, '|') => Dir::S,
+        (Dir::S, 'J') => Dir::W,
+        (Dir::S, 'L') => Dir::E,
+
+        (Dir::E, '-') => Dir::E,
+        (Dir::E, 'J') => Dir::N,
+        (Dir::E, '7') => Dir::S,
+
+        (Dir::W, '-') => Dir::W,
+        (Dir::W, 'F') => Dir::S,
+        (Dir::W, 'L') => Dir::N,
+        _ => return None,
+    };
+    Some((next, dir))
+}
+
+fn get_right_spaces(c: char, dir: Dir) -> Vec<Pos> {
+    match c {
+        '|' => match dir {
+            Dir::N => vec![(1, 0)],
+            Dir::S => vec![(-1, 0)],
+            _ => unreachable!(),
+        },
+        '-' => match dir {
+            Dir::E => vec![(0, 1)],
+            Dir::W => vec![(0, -1)],
+            _ => unreachable!(),
+        },
+        'L' => match dir {
+            Dir::N => vec![(1, -1)],
+            Dir::E => vec![(-1, 0), (-1, 1), (0, 1)],
+            _ => unreachable!(),
+        },
+        'J' => match dir {
+            Dir::W => vec![(-1, -1)],
+            Dir::N => vec![(0, 1), (1, 1), (1, 0)],
+            _ => unreachable!(),
+        },
+        '7' => match dir {
+            Dir::S => vec![(-1, 1)],
+            Dir::W => vec![(0, -1), (1, -1), (1, 0)],
+            _ => unreachable!(),
+        },
+        'F' => match dir {
+            Dir::E => vec![(1, 1)],
+            Dir::S => vec![(-1, 0), (-1, -1), (0, -1)],
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn expand(pipe: &HashSet<Pos>, enclosed: &mut HashSet<Pos>, p: Pos) {
+    if enclosed.contains(&p) {
+        return;
+    }
+    enclosed.insert(p);
+
+    for np in neighbours4(p) {
+        if !pipe.contains(&np) {
+            expand(pipe, enclosed, np);
+        }
+    }
+}
+
+/// Tiles enclosed by the loop.
+fn enclosed(field: &Field) -> HashSet<Pos> {
+    let Field { tilemap, start } = field;
+    let start = *start;
+    let (dir, back) = start_dirs(field);
+    let pipe = pipe_loop(field, dir);
+
+    // the inside is on the right when walking clockwise, a positive signed
+    // area with y pointing down
+    let area: i64 = pipe
+        .iter()
+        .zip(pipe.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum();
+    let dir = if area > 0 { dir } else { back };
+    let pipe: HashSet<Pos> = pipe.into_iter().collect();
+
+    let mut enclosed: HashSet<Pos> = HashSet::new();
+    let (mut cur, mut dir) = mv(dir, tilemap, start);
+    while cur != start {
+        let right = get_right_spaces(tilemap[cur], dir);
+        for r in right
+            .iter()
+            .map(|&n| add(n, cur))
+            .filter(|n| !pipe.contains(n))
+        {
+            expand(&pipe, &mut enclosed, r);
+        }
+        (cur, dir) = mv(dir, tilemap, cur);
+    }
+    enclosed
+}
+
+fn p2(field: &Field) -> i64 {
+    enclosed(field).len() as i64
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Model<'a> = Field;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let mut tilemap = parse_all(grid("|-LJ7F.S"), input)?;
+        let (Some(i), Some(start)) = (input.find('S'), tilemap.find(|&c| c == 'S')) else {
+            return Err(ParseError::new("a start tile `S`", end(input)));
+        };
+        let t = get_start_type(&tilemap, start).ok_or_else(|| {
+            ParseError::new("a start connected to exactly two pipes", char_at(input, i))
+        })?;
+        tilemap[start] = t;
+        let field = Field { tilemap, start };
+
+        // walk the loop once, so that the parts can follow it blindly
+        let (mut cur, mut dir) = (start, start_dirs(&field).0);
+        loop {
+            let Some(next) = follow(dir, &field.tilemap, cur) else {
+                // the tile that doesn't connect, or the last one if the pipe
+                // leads off the map
+                let tile = |(x, y): Pos| {
+                    let line = input.lines().nth(usize::try_from(y).ok()?)?;
+                    Some(char_at(line, usize::try_from(x).ok()?)).filter(|t| !t.is_empty())
+                };
+                let found = tile(dir.step(cur)).or(tile(cur)).unwrap_or(end(input));
+                return Err(ParseError::new("a pipe closing the loop", found));
+            };
+            (cur, dir) = next;
+            if cur == start {
+                break;
+            }
+        }
+        Ok(field)
+    }
+
+    fn part1(field: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(field).into())
+    }
+
+    fn part2(field: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(field).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(field: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        let canvas = Canvas::new(field.tilemap.clone())
+            .overlay(Rgb::YELLOW, None, pipe_loop(field, start_dirs(field).0))
+            .overlay(Rgb::GREEN, Some('I'), enclosed(field));
+        Some(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+";
+
+    const COMPLEX: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const ENCLOSED: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const LARGER: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    const JUNK: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day10::parse(SQUARE).unwrap()), 4);
+        assert_eq!(p1(&Day10::parse(COMPLEX).unwrap()), 8);
+    }
+
+    #[test]
+    fn broken_loop() {
+        let input = "S-7\n|.|\nL-.\n";
+        let err = Day10::parse(input).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(3), Some(3)));
+
+        let input = "S-7\n|.|\nL--\n";
+        let err = Day10::parse(input).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(3), Some(3)));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day10::parse(ENCLOSED).unwrap()), 4);
+        assert_eq!(p2(&Day10::parse(LARGER).unwrap()), 8);
+        assert_eq!(p2(&Day10::parse(JUNK).unwrap()), 10);
+    }
+}
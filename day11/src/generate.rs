@@ -0,0 +1,31 @@
+//! Images `size` pixels wide and high with a few galaxies.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut s = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            s.push(if rng.chance(1, 20) { '#' } else { '.' });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day11;
+
+    /// Each empty row or column between two galaxies adds the same to their
+    /// distance for every extra time wider it gets.
+    #[test]
+    fn linear_expansion() {
+        for (input, answers) in solve_generated::<Day11>(0..20, 40) {
+            assert_eq!((answers[1] - answers[0]) % (1_000_000 - 2), 0, "{input}");
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use aoc_common::combinator::{grid_with, parse_all};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::Pos;
+use aoc_common::grid::one_of;
+use aoc_common::params;
+use aoc_common::parse::end;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+params! {
+    /// How many times wider each empty row and column becomes.
+    pub struct Expansion {
+        pub part1_factor: i64 = 2,
+        pub part2_factor: i64 = 1_000_000,
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.part1_factor < 1 || self.part2_factor < 1 {
+            Err("`part1_factor` and `part2_factor` must be at least 1".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub struct Image<'a> {
+    galaxies: Vec<Pos>,
+    empty_cols: Vec<i64>,
+    empty_rows: Vec<i64>,
+    /// End of the input, where errors about the expanded image point.
+    end: &'a str,
+}
+
+/// Sum of the distances between every pair of galaxies once each empty row
+/// and column is `factor` times wider, `None` if it doesn't fit in an `i64`.
+fn sln(image: &Image, factor: i64) -> Option<i64> {
+    let before = |empty: &[i64], n: i64| empty.iter().filter(|&&e| e < n).count() as i64;
+    let expand = |empty: &[i64], n: i64| before(empty, n).checked_mul(factor - 1)?.checked_add(n);
+
+    let galaxies = image
+        .galaxies
+        .iter()
+        .map(|&(x, y)| Some((expand(&image.empty_cols, x)?, expand(&image.empty_rows, y)?)))
+        .collect::<Option<Vec<Pos>>>()?;
+
+    let mut sum = 0i64;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            let dist = (a.0 - b.0).abs().checked_add((a.1 - b.1).abs())?;
+            sum = sum.checked_add(dist)?;
+        }
+    }
+    Some(sum)
+}
+
+/// [`sln`] for the parts, an error if the distances don't add up in an
+/// `i64`.
+fn expanded_sum(image: &Image, factor: i64) -> ParseResult<i64> {
+    sln(image, factor).ok_or_else(|| {
+        ParseError::new(
+            format!("galaxies close enough to add up their distances {factor} times wider"),
+            image.end,
+        )
+    })
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Model<'a> = Image<'a>;
+    type Params = Expansion;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let map = grid_with(|c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(one_of(".#")),
+        });
+        let map = parse_all(map, input)?;
+        let mut galaxies: Vec<Pos> = map.iter().filter(|(_, &g)| g).map(|(p, _)| p).collect();
+        galaxies.sort();
+
+        let (w, h) = (map.width() as i64, map.height() as i64);
+        let empty_cols = (0..w)
+            .filter(|x| galaxies.iter().all(|g| g.0 != *x))
+            .collect();
+        let empty_rows = (0..h)
+            .filter(|y| galaxies.iter().all(|g| g.1 != *y))
+            .collect();
+
+        Ok(Image {
+            galaxies,
+            empty_cols,
+            empty_rows,
+            end: end(input),
+        })
+    }
+
+    fn part1(image: &Self::Model<'_>, expansion: &Self::Params) -> ParseResult<Answer> {
+        Ok(expanded_sum(image, expansion.part1_factor)?.into())
+    }
+
+    fn part2(image: &Self::Model<'_>, expansion: &Self::Params) -> ParseResult<Answer> {
+        Ok(expanded_sum(image, expansion.part2_factor)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::params::Params;
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(sln(&Day11::parse(EXAMPLE).unwrap(), 2), Some(374));
+    }
+
+    #[test]
+    fn part2() {
+        let image = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(sln(&image, 10), Some(1030));
+        assert_eq!(sln(&image, 100), Some(8410));
+    }
+
+    #[test]
+    fn huge_factor() {
+        let image = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(sln(&image, 1), Some(292));
+        let wide = Expansion {
+            part2_factor: i64::MAX / 4,
+            ..Expansion::default()
+        };
+        assert!(Day11::part1(&image, &wide).is_ok());
+        assert!(Day11::part2(&image, &wide).is_err());
+
+        let flat = Expansion {
+            part1_factor: 0,
+            ..Expansion::default()
+        };
+        assert!(flat.check().is_err());
+    }
+}
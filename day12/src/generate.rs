@@ -0,0 +1,29 @@
+//! Condition records of `size` rows of springs, each hiding at least one
+//! arrangement.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        // a row of damaged groups with operational springs around them,
+        // then some of it forgotten
+        let mut row = ".".repeat(rng.index(0..3));
+        let mut groups = vec![];
+        for _ in 0..rng.index(1..6) {
+            let len = rng.index(1..5);
+            row.push_str(&"#".repeat(len));
+            row.push_str(&".".repeat(rng.index(1..4)));
+            groups.push(len.to_string());
+        }
+        if rng.chance(1, 2) {
+            row.pop();
+        }
+        let row: String = row
+            .chars()
+            .map(|c| if rng.chance(1, 2) { '?' } else { c })
+            .collect();
+        s.push_str(&format!("{row} {}\n", groups.join(",")));
+    }
+    s
+}
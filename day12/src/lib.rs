@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use aoc_common::combinator::{char_in, lines, parse_all, tag, uint, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::parallel;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+#[cfg(test)]
+mod reference;
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Cond {
+    Op,
+    Broken,
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct Spring {
+    springs: Vec<Cond>,
+    rules: Vec<usize>,
+}
+
+fn char_cond(c: char) -> Option<Cond> {
+    match c {
+        '#' => Some(Cond::Broken),
+        '.' => Some(Cond::Op),
+        '?' => Some(Cond::Unknown),
+        _ => None,
+    }
+}
+
+/// A record like `???.### 1,1,3`.
+fn spring<'a>() -> impl Parser<'a, Spring> {
+    let springs = char_in("#.?").map(|c| char_cond(c).unwrap()).many1();
+    springs
+        .skip(tag(" "))
+        .then(uint().sep_by(tag(",")))
+        .map(|(springs, rules)| Spring { springs, rules })
+}
+
+impl Spring {
+    /// The record folded out five times, as in part 2.
+    fn unfold(&self) -> Spring {
+        let mut springs = self.springs.clone();
+        for _ in 1..5 {
+            springs.push(Cond::Unknown);
+            springs.extend(self.springs.iter().cloned());
+        }
+        let rules = self.rules.repeat(5);
+
+        Spring { springs, rules }
+    }
+}
+
+fn can_put(slots: &[Cond], idx: usize, len: usize) -> bool {
+    if slots.len() < idx + len {
+        return false;
+    }
+    if slots[idx..(idx + len)]
+        .iter()
+        .any(|slot| matches!(slot, Cond::Op))
+    {
+        return false;
+    }
+
+    match slots.get(idx + len) {
+        Some(Cond::Unknown) => true,
+        Some(Cond::Op) => true,
+        Some(Cond::Broken) => false,
+        None => true,
+    }
+}
+
+fn solve<'a>(
+    conds: &'a [Cond],
+    rules: &'a [usize],
+    memo: &mut HashMap<(&'a [Cond], &'a [usize]), u64>,
+) -> u64 {
+    let mut perms = 0;
+    let length = rules[0];
+
+    if let Some(res) = memo.get(&(conds, rules)) {
+        return *res;
+    }
+
+    for i in 0..conds.len() {
+        if can_put(conds, i, length) {
+            if rules.len() == 1 {
+                if !(i + length < conds.len()
+                    && conds[i + length + 1..]
+                        .iter()
+                        .any(|x| matches!(x, Cond::Broken)))
+                {
+                    perms += 1;
+                }
+                if matches!(conds[i], Cond::Broken) {
+                    break;
+                }
+                continue;
+            }
+            if i + length == conds.len() {
+                continue;
+            }
+            perms += solve(&conds[i + length + 1..], &rules[1..], memo);
+        }
+        if matches!(conds[i], Cond::Broken) {
+            break;
+        }
+    }
+
+    // let mut memo2 = HashMap::new();
+    // memo2.insert((conds, rules), perms);
+
+    memo.insert((conds, rules), perms);
+    perms
+}
+
+fn arrangements(s: &Spring) -> u64 {
+    let mut memo = HashMap::new();
+    solve(&s.springs, &s.rules, &mut memo)
+}
+
+fn p1(springs: &[Spring]) -> u64 {
+    parallel::map(springs, arrangements).into_iter().sum()
+}
+
+fn p2(springs: &[Spring]) -> u64 {
+    parallel::map(springs, |s| arrangements(&s.unfold()))
+        .into_iter()
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Model<'a> = Vec<Spring>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(lines(spring()), input)
+    }
+
+    fn part1(springs: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(springs).into())
+    }
+
+    fn part2(springs: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(springs).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    fn conds(s: &str) -> Vec<Cond> {
+        s.chars().map(|c| char_cond(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn put_group() {
+        assert!(can_put(&conds("???.###"), 0, 1));
+        assert!(can_put(&conds("???.###"), 4, 3));
+        assert!(can_put(&conds("##."), 0, 2));
+        assert!(!can_put(&conds("###"), 0, 2));
+        assert!(!can_put(&conds("?.?"), 0, 2));
+        assert!(!can_put(&conds("??"), 1, 2));
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day12::parse(EXAMPLE).unwrap()), 21);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day12::parse(EXAMPLE).unwrap()), 525152);
+    }
+}
@@ -0,0 +1,77 @@
+//! Slow but plain solutions to check the memoised counting against: every
+//! way of filling in the unknown springs is tried.
+
+use aoc_common::rng::Rng;
+use aoc_common::solution::Solution;
+
+use crate::{generate, solve, Cond, Day12, Spring};
+
+/// Lengths of the runs of damaged springs.
+fn groups(springs: &[Cond]) -> Vec<usize> {
+    springs
+        .split(|c| *c != Cond::Broken)
+        .map(|run| run.len())
+        .filter(|&len| len > 0)
+        .collect()
+}
+
+fn arrangements(spring: &Spring) -> u64 {
+    let unknown: Vec<_> = (0..spring.springs.len())
+        .filter(|&i| spring.springs[i] == Cond::Unknown)
+        .collect();
+    let mut count = 0;
+    for mask in 0..1u64 << unknown.len() {
+        let mut springs = spring.springs.clone();
+        for (bit, &i) in unknown.iter().enumerate() {
+            springs[i] = if mask >> bit & 1 == 1 {
+                Cond::Broken
+            } else {
+                Cond::Op
+            };
+        }
+        if groups(&springs) == spring.rules {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn fast(spring: &Spring) -> u64 {
+    solve(&spring.springs, &spring.rules, &mut Default::default())
+}
+
+#[test]
+fn matches_fast() {
+    for seed in 0..20 {
+        let input = generate::input(&mut Rng::new(seed), 10);
+        for spring in Day12::parse(&input).unwrap() {
+            assert_eq!(fast(&spring), arrangements(&spring), "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn matches_fast_unfolded() {
+    // few enough unknowns to try them all once folded out
+    let mut rng = Rng::new(0);
+    for _ in 0..100 {
+        let len = rng.index(1..6);
+        let mut row: Vec<_> = (0..len).map(|_| *rng.pick(&['.', '#'])).collect();
+        if !row.contains(&'#') {
+            row[0] = '#';
+        }
+        let truth: String = row.iter().collect();
+        for _ in 0..rng.index(0..3) {
+            row[rng.index(0..len)] = '?';
+        }
+        let rules: Vec<_> = truth
+            .split('.')
+            .filter(|g| !g.is_empty())
+            .map(|g| g.len().to_string())
+            .collect();
+        let line = format!("{} {}", row.iter().collect::<String>(), rules.join(","));
+
+        let spring = Day12::parse(&line).unwrap()[0].unfold();
+        assert_eq!(fast(&spring), arrangements(&spring), "{line}");
+    }
+}
@@ -0,0 +1,115 @@
+//! Notes of `size` patterns of ash and rocks.
+//!
+//! Each pattern mirrors along one line, and along a second one but for a
+//! single smudge that lies outside the part of the pattern the first line
+//! reflects. No other line mirrors it, smudged or not.
+
+use aoc_common::rng::Rng;
+
+/// Copies the rows above row `m` to the rows below it, as far as there are
+/// rows on both sides.
+fn mirror_rows(rows: &mut [Vec<bool>], m: usize) {
+    for o in 0..m.min(rows.len() - m) {
+        rows[m + o] = rows[m - 1 - o].clone();
+    }
+}
+
+fn transpose(rows: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..rows[0].len())
+        .map(|x| rows.iter().map(|r| r[x]).collect())
+        .collect()
+}
+
+/// Cells differing between the rows mirrored along the line above row `m`.
+fn smudges(rows: &[Vec<bool>], m: usize) -> usize {
+    (0..m.min(rows.len() - m))
+        .map(|o| {
+            let (a, b) = (&rows[m - 1 - o], &rows[m + o]);
+            a.iter().zip(b).filter(|(a, b)| a != b).count()
+        })
+        .sum()
+}
+
+/// A pattern with both lines, which random cells can happen to give more.
+fn mirrored(rng: &mut Rng) -> Vec<Vec<bool>> {
+    let (w, h) = (rng.index(5..18), rng.index(5..18));
+    let mut rows: Vec<Vec<bool>> = (0..h)
+        .map(|_| (0..w).map(|_| rng.chance(1, 2)).collect())
+        .collect();
+
+    // a column line off centre, so some columns aren't reflected, and a row
+    // line mirroring both halves of it
+    let col = loop {
+        let m = rng.index(1..w);
+        if 2 * m != w {
+            break m;
+        }
+    };
+    let mut cols = transpose(&rows);
+    mirror_rows(&mut cols, col);
+    rows = transpose(&cols);
+    let row = rng.index(1..h);
+    mirror_rows(&mut rows, row);
+
+    // the smudge breaks the row line only
+    let x = if 2 * col < w {
+        rng.index(2 * col..w)
+    } else {
+        rng.index(0..2 * col - w)
+    };
+    let y = rng.index(row.saturating_sub(h - row)..row);
+    rows[y][x] = !rows[y][x];
+
+    if rng.chance(1, 2) {
+        transpose(&rows)
+    } else {
+        rows
+    }
+}
+
+fn pattern(rng: &mut Rng) -> Vec<Vec<bool>> {
+    loop {
+        let rows = mirrored(rng);
+        let cols = transpose(&rows);
+        let lines = (1..rows.len()).map(|m| smudges(&rows, m));
+        let lines = lines.chain((1..cols.len()).map(|m| smudges(&cols, m)));
+        if lines.filter(|&n| n <= 1).count() == 2 {
+            return rows;
+        }
+    }
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let patterns: Vec<String> = (0..size)
+        .map(|_| {
+            pattern(rng)
+                .iter()
+                .flat_map(|r| r.iter().map(|&b| if b { '#' } else { '.' }).chain(['\n']))
+                .collect()
+        })
+        .collect();
+    patterns.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::rng::Rng;
+    use aoc_common::solution::answers;
+
+    use crate::Day13;
+
+    /// Each pattern mirrors along a line one way and, once the smudge is
+    /// cleaned, along a line the other way.
+    #[test]
+    fn crossing_lines() {
+        for seed in 0..20 {
+            for pattern in super::input(&mut Rng::new(seed), 10).split("\n\n") {
+                let rows: Vec<_> = answers::<Day13>(pattern)
+                    .iter()
+                    .map(|n| n % 100 == 0)
+                    .collect();
+                assert_ne!(rows[0], rows[1], "{pattern}");
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use aoc_common::combinator::{grid, parse_all, sections};
+use aoc_common::error::ParseResult;
+use aoc_common::grid::Grid;
+use aoc_common::parallel;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+/// Columns left of a vertical mirror line, other than `filter`, 0 if none.
+fn vertical(map: &Grid<char>, filter: Option<i64>) -> i64 {
+    let w = map.width();
+
+    'outer: for m in 1..w {
+        let mirror = m.min(w - m);
+        for offset in 0..mirror {
+            if !map.col(m - 1 - offset).eq(map.col(m + offset)) {
+                continue 'outer;
+            }
+        }
+        match filter {
+            Some(f) if f == m as i64 => continue,
+            _ => return m as i64,
+        }
+    }
+
+    0
+}
+
+/// Rows above a horizontal mirror line, other than `filter`, 0 if none.
+fn horizontal(map: &Grid<char>, filter: Option<i64>) -> i64 {
+    let h = map.height();
+
+    'outer: for m in 1..h {
+        let mirror = m.min(h - m);
+        for offset in 0..mirror {
+            if map.row(m - 1 - offset) != map.row(m + offset) {
+                continue 'outer;
+            }
+        }
+        match filter {
+            Some(f) if f == m as i64 => continue,
+            _ => return m as i64,
+        }
+    }
+    0
+}
+
+fn p1(maps: &[Grid<char>]) -> i64 {
+    let mut sum = 0;
+    for map in maps.iter() {
+        sum += vertical(map, None);
+        sum += 100 * horizontal(map, None);
+    }
+
+    sum
+}
+
+/// Summary of the reflection line found by fixing the smudge on `map`.
+fn smudged(map: &Grid<char>) -> i64 {
+    let v = vertical(map, None);
+    let h = horizontal(map, None);
+    for pos in map.positions() {
+        let mut clone = map.clone();
+        let t = &mut clone[pos];
+        if *t == '.' {
+            *t = '#';
+        } else {
+            *t = '.';
+        }
+        let vv = vertical(&clone, Some(v));
+        let hh = horizontal(&clone, Some(h));
+
+        if vv != 0 || hh != 0 {
+            return vv + 100 * hh;
+        }
+    }
+    0
+}
+
+fn p2(maps: &[Grid<char>]) -> i64 {
+    parallel::map(maps, smudged).into_iter().sum()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Model<'a> = Vec<Grid<char>>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(sections(grid(".#")), input)
+    }
+
+    fn part1(maps: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(maps).into())
+    }
+
+    fn part2(maps: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(maps).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day13::parse(EXAMPLE).unwrap()), 405);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day13::parse(EXAMPLE).unwrap()), 400);
+    }
+}
@@ -0,0 +1,41 @@
+//! Platforms `size` tiles wide and high.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size.max(1) {
+        for _ in 0..size.max(1) {
+            s.push(match rng.below(12) {
+                0..=2 => 'O',
+                3 => '#',
+                _ => '.',
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day14;
+
+    /// Tilting north only moves rounded rocks up, and none of them can weigh
+    /// more than the platform is high.
+    #[test]
+    fn loads() {
+        for (input, answers) in solve_generated::<Day14>(0..10, 20) {
+            let h = input.lines().count();
+            let before: usize = (0..)
+                .zip(input.lines())
+                .map(|(y, l)| l.matches('O').count() * (h - y))
+                .sum();
+            let most = input.matches('O').count() * h;
+            assert!(before as i128 <= answers[0], "{input}");
+            assert!(answers.iter().all(|&load| load <= most as i128), "{input}");
+        }
+    }
+}
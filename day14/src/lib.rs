@@ -0,0 +1,179 @@
+use aoc_common::combinator::{grid_with, parse_all};
+use aoc_common::cycle;
+use aoc_common::error::ParseResult;
+use aoc_common::geom::Dir;
+use aoc_common::grid::{one_of, Grid};
+use aoc_common::params;
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+
+mod generate;
+
+params! {
+    pub struct Spins {
+        /// Spin cycles before measuring the load in part 2.
+        pub cycles: usize = 1_000_000_000,
+    }
+}
+
+type Platform = Grid<Option<Rock>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rock {
+    Cube,
+    Round,
+}
+
+fn char_rock(c: char) -> Result<Option<Rock>, String> {
+    match c {
+        '#' => Ok(Some(Rock::Cube)),
+        'O' => Ok(Some(Rock::Round)),
+        '.' => Ok(None),
+        _ => Err(one_of("#O.")),
+    }
+}
+
+fn tilt(mut map: Platform, dir: Dir) -> Platform {
+    // roll the rocks closest to the edge first so they stop the others
+    let mut order: Vec<_> = map.positions().collect();
+    if matches!(dir, Dir::S | Dir::E) {
+        order.reverse();
+    }
+
+    let mut moved = 0;
+    for pos in order {
+        if map[pos] != Some(Rock::Round) {
+            continue;
+        }
+        let mut cur = pos;
+        while let Some(None) = map.get(dir.step(cur)) {
+            cur = dir.step(cur);
+        }
+        if cur != pos {
+            trace!("roll", from = pos, to = cur, dir = dir);
+            moved += 1;
+        }
+        map[pos] = None;
+        map[cur] = Some(Rock::Round);
+    }
+    trace!("tilt", dir = dir, moved = moved, load = calc_load(&map));
+    map
+}
+
+fn tilt_north(map: Platform) -> Platform {
+    tilt(map, Dir::N)
+}
+
+fn tilt_west(map: Platform) -> Platform {
+    tilt(map, Dir::W)
+}
+
+fn tilt_south(map: Platform) -> Platform {
+    tilt(map, Dir::S)
+}
+
+fn tilt_east(map: Platform) -> Platform {
+    tilt(map, Dir::E)
+}
+
+fn calc_load(map: &Platform) -> i64 {
+    let h = map.height() as i64;
+
+    map.iter()
+        .filter(|(_, r)| matches!(r, Some(Rock::Round)))
+        .map(|(pos, _)| h - pos.1)
+        .sum()
+}
+
+fn p1(map: &Platform) -> i64 {
+    let mut map = map.clone();
+    map = tilt_north(map);
+    calc_load(&map)
+}
+
+fn spin(map: &Platform) -> Platform {
+    let map = tilt_north(map.clone());
+    let map = tilt_west(map);
+    let map = tilt_south(map);
+    let map = tilt_east(map);
+    trace!("spin", load = calc_load(&map));
+    map
+}
+
+fn p2(map: &Platform, cycles: usize) -> i64 {
+    calc_load(&cycle::nth(map.clone(), spin, cycles))
+}
+
+fn tile_char(t: &Option<Rock>) -> char {
+    match t {
+        Some(Rock::Round) => 'O',
+        Some(Rock::Cube) => '#',
+        None => '.',
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const EVENTS: &'static [&'static str] = &["roll", "tilt", "spin"];
+    type Model<'a> = Platform;
+    type Params = Spins;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(grid_with(char_rock), input)
+    }
+
+    fn part1(map: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(map).into())
+    }
+
+    fn part2(map: &Self::Model<'_>, spins: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(map, spins.cycles).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    /// The platform after tilting north, with the rounded rocks highlighted.
+    fn render(map: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        let map = tilt_north(map.clone());
+        let round: Vec<_> = map
+            .iter()
+            .filter(|(_, t)| **t == Some(Rock::Round))
+            .map(|(pos, _)| pos)
+            .collect();
+        Some(Canvas::new(map.map(tile_char)).overlay(Rgb::CYAN, None, round))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day14::parse(EXAMPLE).unwrap()), 136);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day14::parse(EXAMPLE).unwrap(), 1_000_000_000), 64);
+    }
+}
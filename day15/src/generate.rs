@@ -0,0 +1,52 @@
+//! Initialization sequences of `size` steps, reusing labels often enough
+//! for lenses to be replaced and removed.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 3 + 1)
+        .map(|_| {
+            (0..rng.index(1..7))
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+
+    let steps: Vec<_> = (0..size.max(1))
+        .map(|_| {
+            let label = rng.pick(&labels);
+            if rng.chance(1, 3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.range(1..10))
+            }
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use aoc_common::solution::{answers, solve_generated};
+
+    use crate::Day15;
+
+    /// Hashes are below 256, and removing every label at the end leaves all
+    /// the boxes empty.
+    #[test]
+    fn emptied_boxes() {
+        for (input, hashed) in solve_generated::<Day15>(0..20, 200) {
+            let steps: Vec<_> = input.trim_end().split(',').collect();
+            assert!(hashed[0] < 256 * steps.len() as i128);
+            let labels: BTreeSet<_> = steps
+                .iter()
+                .map(|s| s.trim_end_matches(|c: char| !c.is_ascii_lowercase()))
+                .collect();
+            let removals: Vec<_> = labels.iter().map(|l| format!("{l}-")).collect();
+            let emptied = format!("{},{}\n", steps.join(","), removals.join(","));
+            assert_eq!(answers::<Day15>(&emptied)[1], 0, "{input}");
+        }
+    }
+}
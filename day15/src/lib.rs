@@ -0,0 +1,121 @@
+use std::array::from_fn;
+
+use aoc_common::combinator::{line, parse_all, preceded, tag, uint, word, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+fn hash(s: &str) -> u32 {
+    s.trim()
+        .chars()
+        .map(|c| c as u32)
+        .fold(0, |acc, c| ((c + acc) * 17) % 256)
+}
+
+/// What a step does to the lenses of the box its label hashes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step<'a> {
+    /// `label-`
+    Remove(&'a str),
+    /// `label=focal`
+    Set(&'a str, u32),
+}
+
+/// A step with its text, which part 1 hashes whole.
+type Model<'a> = Vec<(&'a str, Step<'a>)>;
+
+fn p1(steps: &Model) -> u32 {
+    steps.iter().map(|(text, _)| hash(text)).sum()
+}
+
+fn p2(steps: &Model) -> u32 {
+    let mut boxes: [Vec<(&str, u32)>; 256] = from_fn(|_| vec![]);
+
+    for (_, step) in steps {
+        match *step {
+            Step::Remove(label) => {
+                let lenses = &mut boxes[hash(label) as usize];
+                if let Some(idx) = lenses.iter().position(|(s, _)| *s == label) {
+                    lenses.remove(idx);
+                }
+            }
+            Step::Set(label, focal) => {
+                let lenses = &mut boxes[hash(label) as usize];
+                if let Some(idx) = lenses.iter().position(|(s, _)| *s == label) {
+                    lenses[idx] = (label, focal);
+                } else {
+                    lenses.push((label, focal));
+                }
+            }
+        }
+    }
+
+    boxes
+        .iter()
+        .enumerate()
+        .map(|(box_id, b)| {
+            b.iter()
+                .enumerate()
+                .map(|(slot_id, (_, f))| (box_id as u32 + 1) * (slot_id as u32 + 1) * (*f))
+                .sum::<u32>()
+        })
+        .sum()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Model<'a> = Model<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let focal = tag("-")
+            .map(|_| None)
+            .or(preceded(tag("="), uint()).map(Some));
+        let step = word().then(focal).map(|(label, focal)| match focal {
+            Some(focal) => Step::Set(label, focal),
+            None => Step::Remove(label),
+        });
+        parse_all(line(step.with_text().sep_by(tag(","))), input)
+    }
+
+    fn part1(steps: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(steps).into())
+    }
+
+    fn part2(steps: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(steps).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+    #[test]
+    fn hash_step() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn=1"), 30);
+        assert_eq!(hash("rn"), 0);
+        assert_eq!(hash("qp"), 1);
+    }
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day15::parse(EXAMPLE).unwrap()), 1320);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day15::parse(EXAMPLE).unwrap()), 145);
+    }
+}
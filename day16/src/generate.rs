@@ -0,0 +1,39 @@
+//! Contraptions `size` tiles wide and high.
+
+use aoc_common::rng::Rng;
+
+const DEVICES: [char; 4] = ['/', '\\', '|', '-'];
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size.max(1) {
+        for _ in 0..size.max(1) {
+            s.push(if rng.chance(1, 8) {
+                *rng.pick(&DEVICES)
+            } else {
+                '.'
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day16;
+
+    /// Part 2 tries the beam of part 1 among others.
+    #[test]
+    fn energized_tiles() {
+        for (input, answers) in solve_generated::<Day16>(0..10, 20) {
+            let tiles = input.lines().map(str::len).sum::<usize>() as i128;
+            let [p1, p2] = answers[..] else {
+                panic!("two parts");
+            };
+            assert!(1 <= p1 && p1 <= p2 && p2 <= tiles, "{input}");
+        }
+    }
+}
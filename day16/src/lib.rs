@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use aoc_common::combinator::{grid, parse_all};
+use aoc_common::error::ParseResult;
+use aoc_common::geom::{Dir, Pos};
+use aoc_common::grid::Grid;
+use aoc_common::parallel;
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+
+mod generate;
+
+#[derive(Debug, Clone)]
+pub struct Cave {
+    map: Grid<char>,
+}
+
+impl Cave {
+    fn from_str(s: &str) -> ParseResult<Cave> {
+        let map = parse_all(grid(".|-/\\"), s)?;
+        Ok(Cave { map })
+    }
+}
+
+fn mv(dir: Dir, map: &Grid<char>, p: Pos) -> (Pos, Dir, Option<Dir>) {
+    let next = dir.step(p);
+
+    let mut split = None;
+
+    let dir = match (dir, map.get(next).unwrap_or(&'.')) {
+        (Dir::N, '/') => Dir::E,
+        (Dir::N, '\\') => Dir::W,
+        (Dir::N, '|') => Dir::N,
+        (Dir::N, '-') => {
+            split = Some(Dir::W);
+            Dir::E
+        }
+
+        (Dir::S, '/') => Dir::W,
+        (Dir::S, '\\') => Dir::E,
+        (Dir::S, '|') => Dir::S,
+        (Dir::S, '-') => {
+            split = Some(Dir::W);
+            Dir::E
+        }
+
+        (Dir::E, '/') => Dir::N,
+        (Dir::E, '\\') => Dir::S,
+        (Dir::E, '-') => Dir::E,
+        (Dir::E, '|') => {
+            split = Some(Dir::N);
+            Dir::S
+        }
+
+        (Dir::W, '/') => Dir::S,
+        (Dir::W, '\\') => Dir::N,
+        (Dir::W, '-') => Dir::W,
+        (Dir::W, '|') => {
+            split = Some(Dir::N);
+            Dir::S
+        }
+
+        _ => dir,
+    };
+
+    (next, dir, split)
+}
+
+#[derive(Debug, Clone)]
+struct Ray {
+    p: Pos,
+    d: Dir,
+    past: HashSet<(Pos, Dir)>,
+}
+
+impl Ray {
+    fn new(p: Pos, d: Dir) -> Ray {
+        let past = HashSet::new();
+        Ray { p, d, past }
+    }
+
+    fn mv(&mut self, cave: &Cave) -> (bool, Option<Ray>) {
+        let split;
+        (self.p, self.d, split) = mv(self.d, &cave.map, self.p);
+
+        if !cave.map.in_bounds(self.p) {
+            trace!("end", at = self.p, dir = self.d, looped = false);
+            return (true, None);
+        }
+
+        if !self.past.insert((self.p, self.d)) {
+            trace!("end", at = self.p, dir = self.d, looped = true);
+            return (true, None);
+        }
+        trace!("ray", at = self.p, dir = self.d);
+
+        let newray = if let Some(d) = split {
+            let mut tmp = self.clone();
+            tmp.d = d;
+            Some(tmp)
+        } else {
+            None
+        };
+
+        (false, newray)
+    }
+}
+
+/// Tiles the beam entering from `p` going `d` passes through.
+fn energized(cave: &Cave, p: Pos, d: Dir) -> HashSet<Pos> {
+    trace!("beam", from = p, dir = d);
+    let mut rays = vec![Ray::new(p, d)];
+
+    let mut splits = HashSet::new();
+    let mut tiles = HashSet::new();
+
+    while !rays.is_empty() {
+        let mut next = vec![];
+        // a ray that ended is dropped, with the tiles it went through kept
+        rays.retain_mut(|r| {
+            let (done, newray) = r.mv(cave);
+
+            if let Some(ray) = newray {
+                let new = splits.insert((ray.p, ray.d));
+                trace!("split", at = ray.p, dir = r.d, branch = ray.d, new = new);
+                if new {
+                    next.push(ray);
+                }
+            }
+
+            if done {
+                tiles.extend(r.past.iter().map(|p| p.0));
+            }
+            !done
+        });
+        rays.extend(next);
+    }
+
+    tiles
+}
+
+fn solve(cave: &Cave, p: Pos, d: Dir) -> usize {
+    energized(cave, p, d).len()
+}
+
+fn p1(cave: &Cave) -> usize {
+    solve(cave, (-1, 0), Dir::E)
+}
+
+fn p2(cave: &Cave) -> usize {
+    let (w, h) = (cave.map.width() as i64, cave.map.height() as i64);
+    let starts: Vec<(Pos, Dir)> = (0..w)
+        .flat_map(|n| [((n, -1), Dir::S), ((n, h), Dir::N)])
+        .chain((0..h).flat_map(|n| [((-1, n), Dir::E), ((w, n), Dir::W)]))
+        .collect();
+    parallel::map(&starts, |&(p, d)| solve(cave, p, d))
+        .into_iter()
+        .max()
+        .unwrap()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const EVENTS: &'static [&'static str] = &["beam", "ray", "split", "end"];
+    type Model<'a> = Cave;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        Cave::from_str(input)
+    }
+
+    fn part1(cave: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(cave).into())
+    }
+
+    fn part2(cave: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(cave).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(cave: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        let canvas = Canvas::new(cave.map.clone()).overlay(
+            Rgb::YELLOW,
+            Some('#'),
+            energized(cave, (-1, 0), Dir::E),
+        );
+        Some(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day16::parse(EXAMPLE).unwrap()), 46);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day16::parse(EXAMPLE).unwrap()), 51);
+    }
+}
@@ -0,0 +1,36 @@
+//! City maps `size` blocks wide and high, at least 5 so the ultra crucible
+//! can reach the far corner.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size.max(5) {
+        for _ in 0..size.max(5) {
+            s.push(char::from(b'1' + rng.below(9) as u8));
+        }
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day17;
+
+    /// Every block loses between 1 and 9 heat, and in cities at most 11
+    /// blocks wide both crucibles can get to the far corner through as few
+    /// blocks as possible, the ultra one along the edges.
+    #[test]
+    fn heat_bounds() {
+        for size in [5, 8, 11] {
+            for (input, answers) in solve_generated::<Day17>(0..10, size) {
+                let blocks = 2 * (size as i128 - 1);
+                let fits = |heat: &i128| (blocks..=9 * blocks).contains(heat);
+                assert!(answers.iter().all(fits), "{input}");
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use aoc_common::combinator::{grid_with, parse_all};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{Dir, Pos};
+use aoc_common::grid::Grid;
+use aoc_common::params;
+use aoc_common::parse::char_at;
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::search::{dijkstra, Path};
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+params! {
+    /// Blocks a crucible can move in a line before it has to turn.
+    pub struct Runs {
+        /// Most blocks for the crucibles of part 1.
+        pub max_run: u8 = 3,
+        /// Fewest blocks for the ultra crucibles of part 2.
+        pub ultra_min_run: u8 = 4,
+        /// Most blocks for the ultra crucibles of part 2.
+        pub ultra_max_run: u8 = 10,
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.max_run < 1 || self.ultra_max_run < 1 {
+            Err("`max_run` and `ultra_max_run` must be at least 1".to_string())
+        } else if self.ultra_min_run > self.ultra_max_run {
+            Err("`ultra_min_run` must be at most `ultra_max_run`".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+type HeatMap = Grid<u16>;
+
+pub struct City<'a> {
+    map: HeatMap,
+    /// Digit of the bottom right corner, where errors about reaching it
+    /// point.
+    corner: &'a str,
+}
+
+fn parse_map(s: &str) -> ParseResult<HeatMap> {
+    let map = grid_with(|c| match c.to_digit(10) {
+        Some(heat) => Ok(heat as u16),
+        None => Err("a digit".to_string()),
+    });
+    parse_all(map, s)
+}
+
+fn bottom_right(map: &HeatMap) -> Pos {
+    (map.width() as i64 - 1, map.height() as i64 - 1)
+}
+
+/// Where the crucible is, which way it's going and how many blocks it has
+/// moved that way.
+type Crucible = (Pos, Dir, u8);
+
+const STARTS: [Crucible; 2] = [((0, 0), Dir::E, 0), ((0, 0), Dir::S, 0)];
+
+/// Blocks the crucible can move to with the heat lost on each, moving at
+/// least `min` and at most `max` blocks before turning.
+fn moves(map: &HeatMap, min: u8, max: u8) -> impl Fn(&Crucible) -> Vec<(Crucible, u16)> + '_ {
+    move |&(pos, dir, run)| {
+        let mut moves = vec![];
+        if run < max {
+            moves.push((dir, run + 1));
+        }
+        if run >= min {
+            moves.push((dir.left(), 1));
+            moves.push((dir.right(), 1));
+        }
+        moves
+            .into_iter()
+            .filter_map(|(d, r)| {
+                let next = d.step(pos);
+                map.get(next).map(|&heat| ((next, d, r), heat))
+            })
+            .collect()
+    }
+}
+
+/// Whether the crucible can stop at the bottom right corner, which it can
+/// without moving on a map of one block.
+fn stops(map: &HeatMap, min: u8) -> impl Fn(&Crucible) -> bool {
+    let corner = bottom_right(map);
+    move |&(pos, _, run)| pos == corner && (run >= min || run == 0)
+}
+
+/// Route losing the least heat from the top left to the bottom right corner,
+/// moving at least `min` and at most `max` blocks before turning or stopping.
+fn best_route(map: &HeatMap, min: u8, max: u8) -> Option<Path<Crucible, u16>> {
+    dijkstra(STARTS, moves(map, min, max), stops(map, min))
+}
+
+/// Least heat lost on the way to the bottom right corner, an error if runs
+/// too long for the map, or that never line up with the corner, can't get
+/// there.
+fn min_heat(city: &City, min: u8, max: u8) -> ParseResult<u16> {
+    match best_route(&city.map, min, max) {
+        Some(route) => Ok(route.cost),
+        None => {
+            let expected =
+                format!("a way to the bottom right corner moving {min} to {max} blocks in a line");
+            Err(ParseError::new(expected, city.corner))
+        }
+    }
+}
+
+/// Fewest and most blocks in a line for the crucibles of `part`.
+fn runs_of(runs: &Runs, part: u8) -> (u8, u8) {
+    match part {
+        1 => (1, runs.max_run),
+        _ => (runs.ultra_min_run, runs.ultra_max_run),
+    }
+}
+
+fn p1(city: &City, runs: &Runs) -> ParseResult<u16> {
+    let (min, max) = runs_of(runs, 1);
+    min_heat(city, min, max)
+}
+
+fn p2(city: &City, runs: &Runs) -> ParseResult<u16> {
+    let (min, max) = runs_of(runs, 2);
+    min_heat(city, min, max)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Model<'a> = City<'a>;
+    type Params = Runs;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let map = parse_map(input)?;
+        let text = input.trim_end();
+        let corner = char_at(text, text.len() - 1);
+        Ok(City { map, corner })
+    }
+
+    fn part1(city: &Self::Model<'_>, runs: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(city, runs)?.into())
+    }
+
+    fn part2(city: &Self::Model<'_>, runs: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(city, runs)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(city: &Self::Model<'_>, runs: &Self::Params) -> Option<Canvas> {
+        let (min, max) = runs_of(runs, 1);
+        let route = best_route(&city.map, min, max)?
+            .states
+            .into_iter()
+            .map(|c| c.0);
+        let digits = city
+            .map
+            .map(|&heat| char::from_digit(heat as u32, 10).unwrap());
+        Some(Canvas::new(digits).overlay(Rgb::RED, None, route))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    const UNFORTUNATE: &str = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(
+            p1(&Day17::parse(EXAMPLE).unwrap(), &Runs::default()),
+            Ok(102)
+        );
+    }
+
+    #[test]
+    fn part2() {
+        let runs = Runs::default();
+        assert_eq!(p2(&Day17::parse(EXAMPLE).unwrap(), &runs), Ok(94));
+        assert_eq!(p2(&Day17::parse(UNFORTUNATE).unwrap(), &runs), Ok(71));
+    }
+
+    #[test]
+    fn unreachable_corner() {
+        let city = Day17::parse("1\n").unwrap();
+        assert_eq!(p2(&city, &Runs::default()), Ok(0));
+
+        let runs = Runs {
+            ultra_min_run: 200,
+            ultra_max_run: 200,
+            ..Runs::default()
+        };
+        let city = Day17::parse(EXAMPLE).unwrap();
+        assert!(p1(&city, &runs).is_ok());
+        let err = p2(&city, &runs).unwrap_err().locate(EXAMPLE);
+        assert_eq!((err.line, err.column), (Some(13), Some(13)));
+
+        // the corner is 4 blocks away, past 3 but short of 5
+        let city = Day17::parse("11111\n").unwrap();
+        let runs = |min, max| Runs {
+            ultra_min_run: min,
+            ultra_max_run: max,
+            ..Runs::default()
+        };
+        assert_eq!(p2(&city, &runs(3, 4)), Ok(4));
+        assert!(p2(&city, &runs(3, 3)).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Dig plans around the [`outline`] of a random tree on a `size` by `size`
+//! lattice.
+//!
+//! Both parts follow the same outline with the gaps between its corners
+//! stretched by different amounts, which keeps it from crossing itself: up
+//! to 10 metres for the plain steps and as far as fits in five hex digits
+//! for the colour codes, flipped around at random.
+
+use std::collections::BTreeSet;
+
+use aoc_common::generate::outline;
+use aoc_common::geom::{Dir, Pos};
+use aoc_common::rng::Rng;
+
+/// Random increasing coordinates for each of `xs`, 2 to `max_gap` apart so
+/// the trench never runs alongside itself.
+fn stretch(rng: &mut Rng, xs: &BTreeSet<i64>, max_gap: i64) -> Vec<(i64, i64)> {
+    let mut at = 0;
+    xs.iter()
+        .map(|&x| {
+            at += rng.range(2..max_gap + 1);
+            (x, at)
+        })
+        .collect()
+}
+
+/// Directions and lengths of the steps from each corner to the next.
+fn steps(corners: &[Pos]) -> Vec<(Dir, i64)> {
+    let next = corners.iter().cycle().skip(1);
+    corners
+        .iter()
+        .zip(next)
+        .map(|(&(x0, y0), &(x1, y1))| match (x1 - x0, y1 - y0) {
+            (0, d) if d < 0 => (Dir::N, -d),
+            (0, d) => (Dir::S, d),
+            (d, _) if d < 0 => (Dir::W, -d),
+            (d, _) => (Dir::E, d),
+        })
+        .collect()
+}
+
+/// `corners` with the coordinates along each axis stretched to at most
+/// `max_gap` apart and flipped if asked.
+fn stretched(rng: &mut Rng, corners: &[Pos], max_gap: i64, flip: (bool, bool)) -> Vec<Pos> {
+    let xs = stretch(rng, &corners.iter().map(|p| p.0).collect(), max_gap);
+    let ys = stretch(rng, &corners.iter().map(|p| p.1).collect(), max_gap);
+    let find = |axis: &[(i64, i64)], v: i64, flip: bool| {
+        let (_, at) = axis.iter().find(|&&(x, _)| x == v).unwrap();
+        if flip {
+            -at
+        } else {
+            *at
+        }
+    };
+    corners
+        .iter()
+        .map(|&(x, y)| (find(&xs, x, flip.0), find(&ys, y, flip.1)))
+        .collect()
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let n = (size as i64 / 2).max(2);
+    let cells = outline(rng, n);
+
+    // only the turns, stretching the straight bits in between can't make
+    // them longer than the whole lattice
+    let corners: Vec<Pos> = (0..cells.len())
+        .filter(|&i| {
+            let prev = cells[(i + cells.len() - 1) % cells.len()];
+            let next = cells[(i + 1) % cells.len()];
+            prev.0 != next.0 && prev.1 != next.1
+        })
+        .map(|i| cells[i])
+        .collect();
+
+    let max_gap = 0xfffff / (2 * n);
+    let plain = stretched(rng, &corners, 10, (false, false));
+    let flip = (rng.chance(1, 2), rng.chance(1, 2));
+    let coded = stretched(rng, &corners, max_gap, flip);
+
+    let mut s = String::new();
+    for ((d, l), (dc, lc)) in steps(&plain).into_iter().zip(steps(&coded)) {
+        let c = match d {
+            Dir::N => 'U',
+            Dir::S => 'D',
+            Dir::W => 'L',
+            Dir::E => 'R',
+        };
+        let code = match dc {
+            Dir::E => 0,
+            Dir::S => 1,
+            Dir::W => 2,
+            Dir::N => 3,
+        };
+        s.push_str(&format!("{c} {l} (#{lc:05x}{code})\n"));
+    }
+    s
+}
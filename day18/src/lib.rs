@@ -0,0 +1,138 @@
+use aoc_common::combinator::{bracketed, char_in, hex, lines, parse_all, spaces, uint, Parser};
+use aoc_common::error::ParseResult;
+use aoc_common::geom::{Dir, Pos};
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+#[cfg(test)]
+mod reference;
+
+/// Direction of the last digit of a colour code.
+fn dir_digit(c: char) -> Dir {
+    match c {
+        '3' => Dir::N,
+        '1' => Dir::S,
+        '2' => Dir::W,
+        '0' => Dir::E,
+        _ => unreachable!("not a direction digit: {c}"),
+    }
+}
+
+#[derive(Debug)]
+pub struct Dig {
+    d: Dir,
+    l: i64,
+}
+
+/// A step like `R 6 (#70c710)`, read as in part 1 and as in part 2.
+fn dig<'a>() -> impl Parser<'a, (Dig, Dig)> {
+    let dir = char_in("UDLR").map(|c| Dir::from_char(c).unwrap());
+    let plain = dir.skip(spaces()).then(uint()).map(|(d, l)| Dig { d, l });
+    let code = hex(5)
+        .then(char_in("0123").map(dir_digit))
+        .map(|(l, d)| Dig { d, l: l as i64 });
+    plain.skip(spaces()).then(bracketed("(#", code, ")"))
+}
+
+fn solve<'a>(plan: impl Iterator<Item = &'a Dig>) -> i64 {
+    let mut pos: Pos = (0, 0);
+    let mut points = vec![pos];
+    let mut len = 0;
+
+    for dig in plan {
+        pos = dig.d.steps(pos, dig.l);
+        points.push(pos);
+        len += dig.l;
+    }
+
+    let mut area = 0;
+    for p in points.windows(2) {
+        area += (p[1].1 + p[0].1) * (p[0].0 - p[1].0);
+    }
+    let inner = (area.abs() / 2) - (len / 2) + 1;
+
+    inner + len
+}
+
+/// Every cube dug out along the edge of the lagoon, and the corners where
+/// the digger turns.
+fn trench<'a>(plan: impl Iterator<Item = &'a Dig>) -> (Vec<Pos>, Vec<Pos>) {
+    let mut pos: Pos = (0, 0);
+    let (mut cubes, mut corners) = (vec![], vec![]);
+    for dig in plan {
+        corners.push(pos);
+        cubes.extend((1..=dig.l).map(|n| dig.d.steps(pos, n)));
+        pos = dig.d.steps(pos, dig.l);
+    }
+    (cubes, corners)
+}
+
+/// Each line read both as a plain dig step and through its colour code.
+pub struct Plan {
+    digs: Vec<(Dig, Dig)>,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Model<'a> = Plan;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_all(lines(dig()), input).map(|digs| Plan { digs })
+    }
+
+    fn part1(plan: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(solve(plan.digs.iter().map(|d| &d.0)).into())
+    }
+
+    fn part2(plan: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(solve(plan.digs.iter().map(|d| &d.1)).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(plan: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        let (cubes, corners) = trench(plan.digs.iter().map(|d| &d.0));
+        Some(Canvas::from_points(cubes, '#').overlay(Rgb::RED, None, corners))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn part1() {
+        let plan = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(solve(plan.digs.iter().map(|d| &d.0)), 62);
+    }
+
+    #[test]
+    fn part2() {
+        let plan = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(solve(plan.digs.iter().map(|d| &d.1)), 952408144115);
+    }
+}
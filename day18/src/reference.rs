@@ -0,0 +1,40 @@
+//! Slow but plain solution to check the shoelace formula against: the
+//! trench is dug cube by cube and the ground outside it flood filled.
+
+use std::collections::HashSet;
+
+use aoc_common::geom::{neighbours4, Pos};
+use aoc_common::rng::Rng;
+use aoc_common::search::distances;
+use aoc_common::solution::Solution;
+
+use crate::{generate, trench, Day18, Dig};
+
+fn lagoon<'a>(plan: impl Iterator<Item = &'a Dig>) -> usize {
+    let cubes: HashSet<_> = trench(plan).0.into_iter().collect();
+    let min_x = cubes.iter().map(|p| p.0).min().unwrap() - 1;
+    let max_x = cubes.iter().map(|p| p.0).max().unwrap() + 1;
+    let min_y = cubes.iter().map(|p| p.1).min().unwrap() - 1;
+    let max_y = cubes.iter().map(|p| p.1).max().unwrap() + 1;
+
+    // everything in the box around the trench that the outside can't reach
+    let inside = |p: &Pos| (min_x..=max_x).contains(&p.0) && (min_y..=max_y).contains(&p.1);
+    let outside = distances(
+        [(min_x, min_y)],
+        |&p| neighbours4(p).filter(|n| inside(n) && !cubes.contains(n)),
+        usize::MAX,
+    )
+    .0;
+    let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+    area as usize - outside.len()
+}
+
+#[test]
+fn matches_fast() {
+    for seed in 0..20 {
+        let input = generate::input(&mut Rng::new(seed), 12);
+        let plan = Day18::parse(&input).unwrap();
+        let digs = || plan.digs.iter().map(|d| &d.0);
+        assert_eq!(crate::solve(digs()) as usize, lagoon(digs()), "seed {seed}");
+    }
+}
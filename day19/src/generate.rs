@@ -0,0 +1,46 @@
+//! Systems of about `size` workflows and `size` parts.
+//!
+//! The workflows form a tree from `in`, so every part ends up accepted or
+//! rejected.
+
+use std::collections::HashSet;
+
+use aoc_common::generate::name;
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut used = HashSet::from(["in".to_string()]);
+    let mut pending = vec!["in".to_string()];
+    let mut workflows = vec![];
+    while let Some(wf) = pending.pop() {
+        let mut rules = vec![];
+        for i in 0..rng.range(1..5) + 1 {
+            let dst = if workflows.len() + pending.len() < size && rng.chance(1, 2) {
+                let len = rng.index(2..4);
+                let dst = name(rng, &mut used, len);
+                pending.push(dst.clone());
+                dst
+            } else {
+                rng.pick(&["A", "R"]).to_string()
+            };
+            if i == 0 {
+                // the fallback, written last
+                rules.push(dst);
+                continue;
+            }
+            let category = rng.pick(&['x', 'm', 'a', 's']);
+            let op = rng.pick(&['<', '>']);
+            rules.insert(0, format!("{category}{op}{}:{dst}", rng.range(1..4001)));
+        }
+        workflows.push(format!("{wf}{{{}}}", rules.join(",")));
+    }
+    rng.shuffle(&mut workflows);
+
+    let mut s = workflows.join("\n");
+    s.push_str("\n\n");
+    for _ in 0..size.max(1) {
+        let [x, m, a, t] = [(); 4].map(|_| rng.range(1..4001));
+        s.push_str(&format!("{{x={x},m={m},a={a},s={t}}}\n"));
+    }
+    s
+}
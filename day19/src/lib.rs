@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::combinator::{
+    blank_line, bracketed, char_in, key_value, lines, parse_all, tag, uint, word, Parser,
+};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::graph::Graph;
+use aoc_common::interval::Interval;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+#[cfg(test)]
+mod reference;
+
+/// Ratings of the `x`, `m`, `a` and `s` categories still possible.
+type Ratings = [Interval<usize>; 4];
+
+fn category_index(c: char) -> usize {
+    match c {
+        'x' => 0,
+        'm' => 1,
+        'a' => 2,
+        's' => 3,
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug)]
+pub enum Op {
+    Lt(char, usize),
+    Gt(char, usize),
+}
+
+impl Op {
+    fn apply(&self, part: &Part) -> bool {
+        match self {
+            Op::Lt('x', v) => part.x < *v,
+            Op::Lt('m', v) => part.m < *v,
+            Op::Lt('a', v) => part.a < *v,
+            Op::Lt('s', v) => part.s < *v,
+            Op::Gt('x', v) => part.x > *v,
+            Op::Gt('m', v) => part.m > *v,
+            Op::Gt('a', v) => part.a > *v,
+            Op::Gt('s', v) => part.s > *v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Splits `ratings` into the ones passing the check and the ones failing.
+    fn split(&self, ratings: Ratings) -> (Ratings, Ratings) {
+        let (mut pass, mut fail) = (ratings, ratings);
+        match *self {
+            Op::Lt(c, v) => {
+                let i = category_index(c);
+                (pass[i], fail[i]) = ratings[i].split_at(v);
+            }
+            Op::Gt(c, v) => {
+                let i = category_index(c);
+                // intervals end before `usize::MAX`, so nothing passes it
+                (fail[i], pass[i]) = ratings[i].split_at(v.saturating_add(1));
+            }
+        }
+        (pass, fail)
+    }
+}
+
+#[derive(Debug)]
+pub struct Rule<'a> {
+    check: Option<Op>,
+    dst: &'a str,
+}
+
+impl Rule<'_> {
+    fn apply(&self, part: &Part) -> bool {
+        if let Some(op) = &self.check {
+            op.apply(part)
+        } else {
+            true
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Workflow<'a> {
+    name: &'a str,
+    rules: Vec<Rule<'a>>,
+}
+
+impl Workflow<'_> {
+    fn apply(&self, part: &Part) -> &str {
+        for r in &self.rules {
+            if r.apply(part) {
+                return r.dst;
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct Part {
+    x: usize,
+    m: usize,
+    a: usize,
+    s: usize,
+}
+
+/// A check like `a<2006`.
+fn op<'a>() -> impl Parser<'a, Op> {
+    char_in("xmas")
+        .then(char_in("<>"))
+        .then(uint())
+        .map(|((c, cmp), v)| match cmp {
+            '<' => Op::Lt(c, v),
+            _ => Op::Gt(c, v),
+        })
+}
+
+/// A rule like `a<2006:qkq`, or a fallback like `rfg`.
+fn rule<'a>() -> impl Parser<'a, Rule<'a>> {
+    op().skip(tag(":"))
+        .opt()
+        .then(word())
+        .map(|(check, dst)| Rule { check, dst })
+}
+
+/// A workflow like `px{a<2006:qkq,m>2090:A,rfg}`.
+fn workflow<'a>() -> impl Parser<'a, Workflow<'a>> {
+    let rules = bracketed("{", rule().sep_by(tag(",")), "}");
+    word().then(rules).and_then(|(name, rules)| {
+        // every rule but the last one needs a check, the last one is the fallback
+        let (last, checked) = rules.split_last().unwrap();
+        if let Some(r) = checked.iter().find(|r| r.check.is_none()) {
+            return Err(ParseError::new("a conditional rule", r.dst));
+        }
+        if last.check.is_some() {
+            return Err(ParseError::new("a fallback rule", last.dst));
+        }
+        Ok(Workflow { name, rules })
+    })
+}
+
+/// Ratings like `{x=787,m=2655,a=1222,s=2876}`.
+fn part<'a>() -> impl Parser<'a, Part> {
+    let rating = |name| key_value(tag(name), "=", uint()).map(|(_, v)| v);
+    let ratings = rating("x")
+        .skip(tag(","))
+        .then(rating("m"))
+        .skip(tag(","))
+        .then(rating("a"))
+        .skip(tag(","))
+        .then(rating("s"));
+    bracketed("{", ratings, "}").map(|(((x, m), a), s)| Part { x, m, a, s })
+}
+
+pub struct System<'a> {
+    workflows: HashMap<&'a str, Workflow<'a>>,
+    parts: Vec<Part>,
+}
+
+fn p1(system: &System) -> usize {
+    let System { workflows, parts } = system;
+
+    let mut accept = vec![];
+    let mut reject = vec![];
+
+    for p in parts {
+        let mut wf = &workflows[&"in"];
+
+        loop {
+            let dst = wf.apply(p);
+            if dst == "A" {
+                accept.push(p);
+                break;
+            } else if dst == "R" {
+                reject.push(p);
+                break;
+            } else {
+                wf = &workflows[&dst];
+            }
+        }
+    }
+
+    accept.iter().map(|p| p.x + p.m + p.a + p.s).sum()
+}
+
+fn p2(system: &System) -> usize {
+    let workflows = &system.workflows;
+
+    let mut sum = 0;
+    let mut frontier = vec![("in", [Interval::new(1, 4001); 4])];
+    while let Some((name, mut ratings)) = frontier.pop() {
+        if ratings.iter().any(Interval::is_empty) || name == "R" {
+            continue;
+        }
+        if name == "A" {
+            sum += ratings.iter().map(|r| r.len().unwrap()).product::<usize>();
+            continue;
+        }
+
+        for r in &workflows[name].rules {
+            match &r.check {
+                Some(op) => {
+                    let (pass, fail) = op.split(ratings);
+                    frontier.push((r.dst, pass));
+                    ratings = fail;
+                }
+                None => frontier.push((r.dst, ratings)),
+            }
+        }
+    }
+
+    sum
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Model<'a> = System<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let (workflows, parts) = parse_all(
+            lines(workflow()).skip(blank_line()).then(lines(part())),
+            input,
+        )?;
+
+        if !workflows.iter().any(|w| w.name == "in") {
+            return Err(ParseError::new("an `in` workflow", workflows[0].name));
+        }
+        let mut names = HashSet::new();
+        if let Some(w) = workflows.iter().find(|w| !names.insert(w.name)) {
+            return Err(ParseError::new("a new workflow", w.name));
+        }
+
+        // a part sent back to a workflow it went through would never leave
+        let mut graph = Graph::directed();
+        for w in &workflows {
+            graph.node(w.name);
+            for r in w.rules.iter().filter(|r| !matches!(r.dst, "A" | "R")) {
+                graph.add_edge(w.name, r.dst);
+            }
+        }
+        let mut group = vec![0; graph.len()];
+        for (i, scc) in graph.sccs().into_iter().enumerate() {
+            for id in scc {
+                group[id] = i;
+            }
+        }
+        for w in &workflows {
+            let from = graph.id(&w.name).unwrap();
+            let back = w
+                .rules
+                .iter()
+                .find(|r| graph.id(&r.dst).is_some_and(|to| group[to] == group[from]));
+            if let Some(r) = back {
+                let expected = format!("a workflow not leading back to `{}`", w.name);
+                return Err(ParseError::new(expected, r.dst));
+            }
+        }
+
+        let workflows: HashMap<_, _> = workflows.into_iter().map(|w| (w.name, w)).collect();
+        for r in workflows.values().flat_map(|w| &w.rules) {
+            if !matches!(r.dst, "A" | "R") && !workflows.contains_key(r.dst) {
+                return Err(ParseError::new("a known workflow", r.dst));
+            }
+        }
+
+        Ok(System { workflows, parts })
+    }
+
+    fn part1(system: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(system).into())
+    }
+
+    fn part2(system: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(system).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day19::parse(EXAMPLE).unwrap()), 19114);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day19::parse(EXAMPLE).unwrap()), 167409079868000);
+    }
+
+    #[test]
+    fn largest_threshold() {
+        let input = format!("in{{x>{}:R,A}}\n\n{{x=1,m=2,a=3,s=4}}\n", usize::MAX);
+        let system = Day19::parse(&input).unwrap();
+        assert_eq!(p1(&system), 10);
+        assert_eq!(p2(&system), 256000000000000);
+    }
+
+    #[test]
+    fn stuck_parts() {
+        let input = "in{x<5:px,A}\npx{A}\npx{R}\n\n{x=1,m=2,a=3,s=4}\n";
+        let Err(err) = Day19::parse(input) else {
+            panic!("parsed");
+        };
+        let err = err.locate(input);
+        assert_eq!((err.line, err.column), (Some(3), Some(1)));
+        assert_eq!(err.expected, "a new workflow");
+
+        for (input, column) in [
+            ("in{x<5:in,A}\n\n{x=1,m=2,a=3,s=4}\n", 8),
+            (
+                "in{x<5:px,A}\npx{m>2:qs,R}\nqs{in}\n\n{x=1,m=2,a=3,s=4}\n",
+                8,
+            ),
+        ] {
+            let Err(err) = Day19::parse(input) else {
+                panic!("parsed");
+            };
+            let err = err.locate(input);
+            assert_eq!((err.line, err.column), (Some(1), Some(column)));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Slow but plain solution to check the range splitting against: the ratings
+//! are cut into blocks at every number a rule compares against, and one
+//! part from each block is sent through the workflows like in part 1.
+
+use aoc_common::rng::Rng;
+use aoc_common::solution::Solution;
+
+use crate::{category_index, generate, Day19, Op, Part, System};
+
+fn accepted(system: &System, part: &Part) -> bool {
+    let mut wf = &system.workflows["in"];
+    loop {
+        match wf.apply(part) {
+            "A" => return true,
+            "R" => return false,
+            dst => wf = &system.workflows[dst],
+        }
+    }
+}
+
+fn p2(system: &System) -> usize {
+    // the first rating of each block, for each category
+    let mut cuts = [(); 4].map(|_| vec![1, 4001]);
+    for r in system.workflows.values().flat_map(|w| &w.rules) {
+        match r.check {
+            Some(Op::Lt(c, v)) => cuts[category_index(c)].push(v),
+            Some(Op::Gt(c, v)) => cuts[category_index(c)].push(v + 1),
+            None => {}
+        }
+    }
+    let blocks = cuts.map(|mut cuts| {
+        cuts.retain(|v| (1..=4001).contains(v));
+        cuts.sort();
+        cuts.dedup();
+        cuts.windows(2)
+            .map(|w| (w[0], w[1] - w[0]))
+            .collect::<Vec<_>>()
+    });
+
+    let mut sum = 0;
+    for &(x, xs) in &blocks[0] {
+        for &(m, ms) in &blocks[1] {
+            for &(a, as_) in &blocks[2] {
+                for &(s, ss) in &blocks[3] {
+                    if accepted(system, &Part { x, m, a, s }) {
+                        sum += xs * ms * as_ * ss;
+                    }
+                }
+            }
+        }
+    }
+    sum
+}
+
+#[test]
+fn matches_fast() {
+    for seed in 0..20 {
+        let input = generate::input(&mut Rng::new(seed), 6);
+        let system = Day19::parse(&input).unwrap();
+        assert_eq!(crate::p2(&system), p2(&system), "seed {seed}");
+    }
+}
@@ -0,0 +1,93 @@
+//! Machines wired like the puzzle's: the broadcaster starts a few binary
+//! counters of flip-flops, each with a conjunction that fires and resets it
+//! when it reaches its number, and those feed `rx` through inverters and a
+//! last conjunction. The counters have `size / 4` bits, between 2 and 12.
+
+use std::collections::HashSet;
+
+use aoc_common::generate::name;
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let bits = (size / 4).clamp(2, 12);
+    let counters = rng.index(2..5);
+    let mut used = HashSet::from(["rx".to_string()]);
+    let last = name(rng, &mut used, 2);
+
+    let mut modules = vec![format!("&{last} -> rx")];
+    let mut starts = vec![];
+    for _ in 0..counters {
+        // its number always has the top and bottom bits set
+        let number = (1 << (bits - 1)) | 1 | (rng.below(1 << bits) as usize & !1);
+        let flips: Vec<_> = (0..bits).map(|_| name(rng, &mut used, 2)).collect();
+        let hub = name(rng, &mut used, 2);
+        let inverter = name(rng, &mut used, 2);
+
+        let mut resets = vec![flips[0].clone()];
+        for (i, flip) in flips.iter().enumerate() {
+            let mut dsts = vec![];
+            if let Some(next) = flips.get(i + 1) {
+                dsts.push(next.clone());
+            }
+            if number >> i & 1 == 1 {
+                dsts.push(hub.clone());
+            } else {
+                resets.push(flip.clone());
+            }
+            rng.shuffle(&mut dsts);
+            modules.push(format!("%{flip} -> {}", dsts.join(", ")));
+        }
+        resets.push(inverter.clone());
+        rng.shuffle(&mut resets);
+        modules.push(format!("&{hub} -> {}", resets.join(", ")));
+        modules.push(format!("&{inverter} -> {last}"));
+        starts.push(flips[0].clone());
+    }
+    modules.push(format!("broadcaster -> {}", starts.join(", ")));
+    rng.shuffle(&mut modules);
+
+    modules.iter().map(|m| format!("{m}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use aoc_common::math::lcm;
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day20;
+
+    /// Each counter sends a low pulse on towards `rx` when it reaches its
+    /// number, made of the bits of the flip-flops wired to its conjunction,
+    /// so they all do together first after the least common multiple.
+    #[test]
+    fn counters() {
+        for (input, answers) in solve_generated::<Day20>(0..10, 24) {
+            let dsts: HashMap<_, Vec<_>> = input
+                .lines()
+                .map(|l| {
+                    let (module, dsts) = l.split_once(" -> ").unwrap();
+                    (
+                        module.trim_start_matches(['%', '&']),
+                        dsts.split(", ").collect(),
+                    )
+                })
+                .collect();
+            let flip = |m: &&str| input.contains(&format!("%{m} "));
+            let mut presses = 1;
+            for &first in &dsts["broadcaster"] {
+                let (mut number, mut bit, mut at) = (0u64, 0, Some(first));
+                while let Some(m) = at {
+                    if !dsts[m].iter().all(flip) {
+                        number |= 1 << bit;
+                    }
+                    bit += 1;
+                    at = dsts[m].iter().copied().find(flip);
+                }
+                presses = lcm(presses, number).unwrap();
+            }
+            assert_eq!(answers[1], presses as i128, "{input}");
+        }
+    }
+}
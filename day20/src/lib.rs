@@ -0,0 +1,390 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use aoc_common::combinator::{char_in, lines, parse_all, tag, word, Parser};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::graph::Graph;
+use aoc_common::math::sync;
+use aoc_common::params;
+use aoc_common::parse::end;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+use aoc_common::trace::Field;
+
+mod generate;
+
+params! {
+    pub struct Button {
+        /// Button presses to count the pulses of in part 1.
+        pub presses: usize = 1000,
+    }
+}
+
+pub type Modules<'a> = HashMap<&'a str, Module<'a>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pulse {
+    L,
+    H,
+}
+
+impl Field for Pulse {
+    fn text(&self) -> String {
+        match self {
+            Pulse::L => "low".to_string(),
+            Pulse::H => "high".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ModType<'a> {
+    F(bool),
+    C(BTreeMap<&'a str, Pulse>),
+    B,
+}
+
+#[derive(Debug, Clone)]
+pub struct Module<'a> {
+    name: &'a str,
+    m_type: ModType<'a>,
+    dsts: Vec<&'a str>,
+}
+
+/// A module like `%a -> inv, con`, `&inv -> b` or `broadcaster -> a`.
+fn module<'a>() -> impl Parser<'a, Module<'a>> {
+    let m_type = char_in("%&").opt().map(|c| match c {
+        Some('%') => ModType::F(false),
+        Some(_) => ModType::C(BTreeMap::new()),
+        None => ModType::B,
+    });
+    m_type
+        .then(word())
+        .skip(tag(" -> "))
+        .then(word().sep_by(tag(", ")))
+        .map(|((m_type, name), dsts)| Module { name, m_type, dsts })
+}
+
+pub struct Machine<'a> {
+    modules: Modules<'a>,
+    wiring: Graph<&'a str>,
+    /// End of the input, where errors about missing modules point.
+    end: &'a str,
+}
+
+impl<'a> Machine<'a> {
+    fn new(modules: Vec<Module<'a>>, end: &'a str) -> Machine<'a> {
+        let mut wiring = Graph::directed();
+        for m in &modules {
+            wiring.node(m.name);
+            for &d in &m.dsts {
+                wiring.add_edge(m.name, d);
+            }
+        }
+
+        let mut modules: Modules = modules.into_iter().map(|m| (m.name, m)).collect();
+        // conjunctions start out remembering a low pulse from every input
+        for (name, m) in &mut modules {
+            if let ModType::C(ref mut inputs) = m.m_type {
+                let id = wiring.id(name).unwrap();
+                for i in wiring.incoming(id) {
+                    inputs.insert(wiring.label(i), Pulse::L);
+                }
+            }
+        }
+
+        Machine {
+            modules,
+            wiring,
+            end,
+        }
+    }
+
+    /// Modules sending pulses to `name`, each once even if it lists `name`
+    /// more than once.
+    fn inputs(&self, name: &str) -> Vec<&'a str> {
+        let Some(id) = self.wiring.id(&name) else {
+            return vec![];
+        };
+        let mut inputs: Vec<_> = self
+            .wiring
+            .incoming(id)
+            .map(|i| *self.wiring.label(i))
+            .collect();
+        inputs.sort_unstable();
+        inputs.dedup();
+        inputs
+    }
+
+    /// Modules with a way to `name`, `name` included. They only get pulses
+    /// from each other, so pressing the button changes them the same
+    /// without the rest of the machine.
+    fn upstream(&self, name: &str) -> Modules<'a> {
+        let mut todo: Vec<_> = self.wiring.id(&name).into_iter().collect();
+        let mut seen = vec![false; self.wiring.len()];
+        let mut modules = Modules::new();
+        while let Some(id) = todo.pop() {
+            if std::mem::replace(&mut seen[id], true) {
+                continue;
+            }
+            if let Some(m) = self.modules.get(self.wiring.label(id)) {
+                modules.insert(m.name, m.clone());
+            }
+            todo.extend(self.wiring.incoming(id));
+        }
+        modules
+    }
+}
+
+fn p1(machine: &Machine, presses: usize) -> usize {
+    let mut modules = machine.modules.clone();
+
+    let mut h = 0;
+    let mut l = 0;
+    for n in 1..=presses {
+        trace!("press", n = n);
+        let signals = press(&mut modules);
+        let ll = signals
+            .iter()
+            .filter(|(_, _, p)| matches!(p, Pulse::L))
+            .count();
+        let hh = signals
+            .iter()
+            .filter(|(_, _, p)| matches!(p, Pulse::H))
+            .count();
+        h += hh;
+        l += ll;
+    }
+
+    h * l
+}
+
+fn press<'a>(modules: &mut Modules<'a>) -> Vec<(&'a str, &'a str, Pulse)> {
+    let mut queue = VecDeque::from([("button", "broadcaster", Pulse::L)]);
+
+    let mut signals = vec![];
+
+    while let Some((src, dst, p)) = queue.pop_back() {
+        trace!("pulse", from = src, to = dst, pulse = p);
+        signals.push((src, dst, p));
+
+        let m = modules.get_mut(&dst);
+        if m.is_none() {
+            continue;
+        }
+        let m = m.unwrap();
+        match m.m_type {
+            ModType::F(ref mut f) => {
+                if matches!(p, Pulse::L) {
+                    for d in &m.dsts {
+                        if *f {
+                            queue.push_front((m.name, d, Pulse::L));
+                        } else {
+                            queue.push_front((m.name, d, Pulse::H));
+                        }
+                    }
+                    *f = !*f;
+                }
+            }
+            ModType::C(ref mut mem) => {
+                mem.insert(src, p);
+                if mem.values().all(|past| matches!(past, Pulse::H)) {
+                    for d in &m.dsts {
+                        queue.push_front((m.name, d, Pulse::L));
+                    }
+                } else {
+                    for d in &m.dsts {
+                        queue.push_front((m.name, d, Pulse::H));
+                    }
+                }
+            }
+            ModType::B => {
+                for d in &m.dsts {
+                    queue.push_front((m.name, d, p));
+                }
+            }
+        }
+    }
+    signals
+}
+
+/// Modules `rx` waits on: the inputs of the one module feeding it.
+fn watched<'a>(machine: &Machine<'a>) -> ParseResult<Vec<&'a str>> {
+    match machine.inputs("rx")[..] {
+        [feeder] => Ok(machine.inputs(feeder)),
+        [] => Err(ParseError::new("a module sending to `rx`", machine.end)),
+        [_, other, ..] => Err(ParseError::new("a single module sending to `rx`", other)),
+    }
+}
+
+/// Presses on which `name` first gets a low pulse and the next one, found
+/// by pressing the button for the modules leading to it alone. `None` if it
+/// never gets two, known once those modules are back in a state they were
+/// in.
+fn lows(machine: &Machine, name: &str) -> Option<(u64, u64)> {
+    let mut modules = machine.upstream(name);
+    let mut names: Vec<_> = modules.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut seen = HashMap::new();
+    let mut first = None;
+    for i in 1.. {
+        let state: Vec<bool> = names
+            .iter()
+            .flat_map(|n| match &modules[n].m_type {
+                ModType::F(on) => vec![*on],
+                ModType::C(mem) => mem.values().map(|&p| p == Pulse::H).collect(),
+                ModType::B => vec![],
+            })
+            .collect();
+        if let Some(start) = seen.insert(state, i) {
+            // the presses from `start` on repeat every `i - start`
+            return first.filter(|&f| f >= start).map(|f| (f, f + i - start));
+        }
+
+        trace!("press", n = i);
+        let signals = press(&mut modules);
+        if signals
+            .iter()
+            .any(|&(_, dst, p)| dst == name && p == Pulse::L)
+        {
+            trace!("low", module = name, press = i);
+            match first {
+                Some(f) => return Some((f, i)),
+                None => first = Some(i),
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Presses until `rx` gets a low pulse, when the lows its feeder waits on
+/// line up.
+fn p2(machine: &Machine) -> ParseResult<u64> {
+    let mut cycles = vec![];
+    for name in watched(machine)? {
+        let Some((first, next)) = lows(machine, name) else {
+            return Err(ParseError::new(
+                "a module that keeps getting low pulses",
+                name,
+            ));
+        };
+        cycles.push((first, next - first));
+    }
+    sync(&cycles).ok_or_else(|| {
+        ParseError::new(
+            "inputs of the module feeding `rx` that line up",
+            machine.end,
+        )
+    })
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const EVENTS: &'static [&'static str] = &["press", "pulse", "low"];
+    type Model<'a> = Machine<'a>;
+    type Params = Button;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let modules = parse_all(lines(module()), input)?;
+        let mut names = HashSet::new();
+        if let Some(m) = modules.iter().find(|m| !names.insert(m.name)) {
+            return Err(ParseError::new("a new module", m.name));
+        }
+        Ok(Machine::new(modules, end(input)))
+    }
+
+    /// Part 2 needs `rx` fed by a single module. The examples have no `rx`.
+    fn check(machine: &Self::Model<'_>, part: u8, _params: &Self::Params) -> ParseResult<()> {
+        if part == 2 {
+            watched(machine)?;
+        }
+        Ok(())
+    }
+
+    fn part1(machine: &Self::Model<'_>, button: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(machine, button.presses).into())
+    }
+
+    fn part2(machine: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(machine)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    const EXAMPLE2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    // the examples have no `rx` module, so there is nothing to check part 2
+    // against
+    #[test]
+    fn part1() {
+        let machine = Day20::parse(EXAMPLE1).unwrap();
+        assert_eq!(p1(&machine, 1), 8 * 4);
+        assert_eq!(p1(&machine, 1000), 32000000);
+        assert_eq!(p1(&Day20::parse(EXAMPLE2).unwrap(), 1000), 11687500);
+    }
+
+    #[test]
+    fn unfed_rx() {
+        let machine = Day20::parse(EXAMPLE2).unwrap();
+        assert!(Day20::check(&machine, 1, &Button::default()).is_ok());
+        let err = Day20::check(&machine, 2, &Button::default()).unwrap_err();
+        assert_eq!(err.expected, "a module sending to `rx`");
+
+        let input = "broadcaster -> a\n%a -> inv\n&inv -> con\n&con -> rx\n";
+        let machine = Day20::parse(input).unwrap();
+        assert!(Day20::check(&machine, 2, &Button::default()).is_ok());
+        assert_eq!(p2(&machine), Ok(2));
+
+        let input = "broadcaster -> a, b\n%a -> rx\n&b -> rx\n";
+        let err = Day20::check(&Day20::parse(input).unwrap(), 2, &Button::default());
+        assert_eq!(err.unwrap_err().found, "b");
+
+        // `b` only ever hears low pulses, so it never sends one
+        let input = "broadcaster -> b\n&b -> inv\n&inv -> con\n&con -> rx\n";
+        let machine = Day20::parse(input).unwrap();
+        assert!(Day20::check(&machine, 2, &Button::default()).is_ok());
+        let err = p2(&machine).unwrap_err().locate(input);
+        assert_eq!((err.line, err.column), (Some(2), Some(7)));
+    }
+
+    #[test]
+    fn repeated_modules() {
+        let input = "broadcaster -> a\n%a -> inv\n&inv -> con\n%a -> con\n&con -> rx\n";
+        let Err(err) = Day20::parse(input) else {
+            panic!("parsed");
+        };
+        let err = err.locate(input);
+        assert_eq!((err.line, err.column), (Some(4), Some(2)));
+        assert_eq!(err.expected, "a new module");
+
+        // `inv` sends to `con` twice, which still only waits on it once
+        let input = "broadcaster -> a\n%a -> inv\n&inv -> con, con\n&con -> rx\n";
+        let machine = Day20::parse(input).unwrap();
+        assert_eq!(watched(&machine), Ok(vec!["inv"]));
+        assert_eq!(p2(&machine), Ok(2));
+    }
+}
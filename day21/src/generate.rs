@@ -0,0 +1,26 @@
+//! Gardens like the puzzle's, an odd `size` tiles wide and high, at least
+//! 11, with the start in the middle.
+//!
+//! The middle row and column and the edges are kept clear of rocks, as in
+//! the puzzle, which makes the plots reachable in the infinite garden grow
+//! regularly enough to extrapolate.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = (size.max(11) - 1) / 2 * 2 + 1;
+    let mid = size / 2;
+    let mut s = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            let clear = x == 0 || y == 0 || x == size - 1 || y == size - 1 || x == mid || y == mid;
+            s.push(match (x, y) {
+                _ if x == mid && y == mid => 'S',
+                _ if !clear && rng.chance(1, 6) => '#',
+                _ => '.',
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use aoc_common::combinator::{grid, parse_all};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{neighbours4, Pos};
+use aoc_common::grid::Grid;
+use aoc_common::params;
+use aoc_common::parse::{char_at, end};
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::search::distances;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+#[cfg(test)]
+mod reference;
+
+params! {
+    pub struct Steps {
+        /// Steps the elf takes in part 1.
+        pub steps: usize = 64,
+        /// Steps the elf takes in part 2, on the infinite map.
+        pub far_steps: usize = 26501365,
+    }
+}
+
+pub struct Garden<'a> {
+    map: Grid<char>,
+    start: Pos,
+    /// The input, to point at tiles in errors.
+    text: &'a str,
+}
+
+impl<'a> Garden<'a> {
+    fn tile(&self, (x, y): Pos) -> &'a str {
+        let line = self.text.lines().nth(y as usize).unwrap_or_default();
+        char_at(line, x as usize)
+    }
+}
+
+fn parse_map(s: &str) -> ParseResult<Garden<'_>> {
+    let mut map = parse_all(grid(".#S"), s)?;
+    let Some(start) = map.find(|&c| c == 'S') else {
+        return Err(ParseError::new("a start tile `S`", end(s)));
+    };
+    map[start] = '.';
+    Ok(Garden {
+        map,
+        start,
+        text: s,
+    })
+}
+
+/// Fewest steps to every plot at most `max` steps away, on the map repeated
+/// in every direction if `wrap`.
+fn steps_to(garden: &Garden, max: usize, wrap: bool) -> HashMap<Pos, usize> {
+    let Garden { map, start, .. } = garden;
+    let open = |p: Pos| {
+        let tile = if wrap {
+            Some(map.get_wrapping(p))
+        } else {
+            map.get(p)
+        };
+        tile == Some(&'.')
+    };
+    distances([*start], |&p| neighbours4(p).filter(|&n| open(n)), max).0
+}
+
+/// Number of plots reachable in exactly `steps` steps, for every number of
+/// steps up to `max`, on the map repeated in every direction if `wrap`.
+fn reachable_counts(garden: &Garden, max: usize, wrap: bool) -> Vec<usize> {
+    let dist = steps_to(garden, max, wrap);
+
+    // a plot reached in `d` steps can be reached again in `d + 2`, `d + 4`...
+    let mut counts = vec![0; max + 1];
+    for &d in dist.values() {
+        counts[d] += 1;
+    }
+    for i in 2..=max {
+        counts[i] += counts[i - 2];
+    }
+    counts
+}
+
+/// Number of plots reachable in exactly `steps` steps, without wrapping.
+fn reachable(garden: &Garden, steps: usize) -> usize {
+    reachable_counts(garden, steps, false)[steps]
+}
+
+/// Steps [`p2`] counts outright before extrapolating: the reachable plots
+/// grow the same way every width of the garden once they leave the copy of
+/// the map around the start, this is two widths past that.
+fn sample_len(garden: &Garden) -> usize {
+    let width = garden.map.width();
+    2 * width + width / 2
+}
+
+fn p2(garden: &Garden, tgt: usize) -> usize {
+    let check = garden.map.width();
+    let sample = sample_len(garden);
+    let counts = reachable_counts(garden, sample, true);
+    if let Some(&count) = counts.get(tgt) {
+        return count;
+    }
+
+    let mut past = vec![];
+    let mut prev = counts[0];
+
+    for (i, &next) in counts.iter().enumerate().skip(1) {
+        past.push(next - prev);
+        prev = next;
+
+        // guess work here, could be optimized
+        if i >= sample {
+            let mut lut = vec![];
+            let mut lut_d = vec![];
+            for idx in (0..check).rev() {
+                lut_d.push(past[i - 1 - idx] - past[i - 1 - check - idx]);
+                lut.push(past[i - 1 - idx]);
+            }
+            let mut cur = next;
+            for x in 0.. {
+                let ndiff = lut[x % check] + lut_d[x % check];
+                cur += ndiff;
+                lut[x % check] = ndiff;
+                if i + x + 1 == tgt {
+                    return cur;
+                }
+            }
+        }
+    }
+
+    unreachable!();
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Model<'a> = Garden<'a>;
+    type Params = Steps;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        parse_map(input)
+    }
+
+    /// Part 2 extrapolates from the puzzle's square garden with the start in
+    /// the middle of a clear row and column, which lets the elf reach every
+    /// copy of the map the same way.
+    fn check(garden: &Self::Model<'_>, part: u8, steps: &Self::Params) -> ParseResult<()> {
+        if part == 1 || steps.far_steps <= sample_len(garden) {
+            return Ok(());
+        }
+        let (w, h) = (garden.map.width(), garden.map.height());
+        if w != h || w % 2 == 0 {
+            return Err(ParseError::new(
+                "a square garden an odd number of tiles wide",
+                end(garden.text),
+            ));
+        }
+        let mid = (w / 2) as i64;
+        if garden.start != (mid, mid) {
+            return Err(ParseError::new(
+                "the start in the middle of the garden",
+                garden.tile(garden.start),
+            ));
+        }
+        let cross = (0..w as i64).flat_map(|i| [(i, mid), (mid, i)]);
+        if let Some(rock) = cross.into_iter().find(|&p| garden.map[p] == '#') {
+            return Err(ParseError::new(
+                "a clear row and column through the start",
+                garden.tile(rock),
+            ));
+        }
+        Ok(())
+    }
+
+    fn part1(garden: &Self::Model<'_>, steps: &Self::Params) -> ParseResult<Answer> {
+        Ok(reachable(garden, steps.steps).into())
+    }
+
+    fn part2(garden: &Self::Model<'_>, steps: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(garden, steps.far_steps).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(garden: &Self::Model<'_>, steps: &Self::Params) -> Option<Canvas> {
+        let plots = steps_to(garden, steps.steps, false)
+            .into_iter()
+            .filter(|&(_, d)| d % 2 == steps.steps % 2)
+            .map(|(p, _)| p);
+        let canvas = Canvas::new(garden.map.clone())
+            .overlay(Rgb::GREEN, Some('O'), plots)
+            .overlay(Rgb::BLUE, Some('S'), [garden.start]);
+        Some(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn part1() {
+        let garden = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(reachable(&garden, 1), 2);
+        assert_eq!(reachable(&garden, 2), 4);
+        assert_eq!(reachable(&garden, 3), 6);
+        assert_eq!(reachable(&garden, 6), 16);
+    }
+
+    // part 2 extrapolates from the real input's clear rows and columns
+    // through the start, which the example doesn't have
+    #[test]
+    fn not_extrapolated() {
+        let garden = Day21::parse(EXAMPLE).unwrap();
+        let steps = Steps::default();
+        assert!(Day21::check(&garden, 1, &steps).is_ok());
+        let err = Day21::check(&garden, 2, &steps)
+            .unwrap_err()
+            .locate(EXAMPLE);
+        assert_eq!((err.line, err.column), (Some(6), Some(2)));
+
+        // few enough steps are counted outright
+        let near = Steps {
+            far_steps: 27,
+            ..steps
+        };
+        assert!(Day21::check(&garden, 2, &near).is_ok());
+
+        for (input, at) in [("...\n.S.\n", (3, 1)), ("...\nS..\n...\n", (2, 1))] {
+            let garden = Day21::parse(input).unwrap();
+            let err = Day21::check(&garden, 2, &steps).unwrap_err().locate(input);
+            assert_eq!((err.line, err.column), (Some(at.0), Some(at.1)));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Slow but plain solution to check the extrapolation against: a
+//! breadth-first search over the garden repeated in every direction, as far
+//! as the elf can walk.
+
+use std::collections::{HashMap, VecDeque};
+
+use aoc_common::geom::neighbours4;
+use aoc_common::rng::Rng;
+use aoc_common::solution::Solution;
+
+use crate::{generate, Day21, Garden};
+
+fn reachable(garden: &Garden, steps: usize) -> usize {
+    let mut dist = HashMap::from([(garden.start, 0)]);
+    let mut queue = VecDeque::from([garden.start]);
+    while let Some(p) = queue.pop_front() {
+        let d = dist[&p];
+        if d == steps {
+            continue;
+        }
+        for n in neighbours4(p) {
+            if *garden.map.get_wrapping(n) == '.' && !dist.contains_key(&n) {
+                dist.insert(n, d + 1);
+                queue.push_back(n);
+            }
+        }
+    }
+    // a plot reached in fewer steps can be reached again two steps later
+    dist.values().filter(|&&d| d % 2 == steps % 2).count()
+}
+
+#[test]
+fn matches_fast() {
+    for (seed, size) in [(0, 11), (1, 13), (2, 15), (3, 17), (4, 141)] {
+        let mut rng = Rng::new(seed);
+        let input = generate::input(&mut rng, size);
+        let garden = Day21::parse(&input).unwrap();
+        // past the steps counted outright, so it extrapolates
+        let steps = 3 * size + rng.index(0..40);
+        assert_eq!(
+            crate::p2(&garden, steps),
+            reachable(&garden, steps),
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn tiny_garden() {
+    // a single plot, where the extrapolation starts right away
+    let garden = Day21::parse("S\n").unwrap();
+    for steps in [3, 20, 101] {
+        assert_eq!(crate::p2(&garden, steps), reachable(&garden, steps));
+    }
+}
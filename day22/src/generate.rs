@@ -0,0 +1,69 @@
+//! Snapshots of `size` bricks up to 4 cubes long over a 10 by 10 area, not
+//! overlapping but mostly still in the air.
+
+use std::collections::HashSet;
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut taken = HashSet::new();
+    let mut s = String::new();
+    let mut placed = 0;
+    while placed < size.max(1) {
+        let a = (
+            rng.range(0..10),
+            rng.range(0..10),
+            rng.range(1..2 * size as i64 + 2),
+        );
+        let len = rng.range(0..4);
+        let b = match rng.below(3) {
+            0 => (a.0 + len, a.1, a.2),
+            1 => (a.0, a.1 + len, a.2),
+            _ => (a.0, a.1, a.2 + len),
+        };
+        if b.0 >= 10 || b.1 >= 10 {
+            continue;
+        }
+        let cubes: Vec<_> = (0..=len)
+            .map(|i| {
+                (
+                    a.0 + (b.0 - a.0).signum() * i,
+                    a.1 + (b.1 - a.1).signum() * i,
+                    a.2 + (b.2 - a.2).signum() * i,
+                )
+            })
+            .collect();
+        if cubes.iter().any(|c| taken.contains(c)) {
+            continue;
+        }
+        taken.extend(cubes);
+        placed += 1;
+        s.push_str(&format!(
+            "{},{},{}~{},{},{}\n",
+            a.0, a.1, a.2, b.0, b.1, b.2
+        ));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day22;
+
+    /// The top brick can always go, and every other brick that can't makes
+    /// at least one fall and at most all the rest.
+    #[test]
+    fn falls() {
+        for (input, answers) in solve_generated::<Day22>(0..10, 60) {
+            let bricks = input.lines().count() as i128;
+            let [safe, falls] = answers[..] else {
+                panic!("two parts");
+            };
+            let held = bricks - safe;
+            assert!(safe >= 1, "{input}");
+            assert!((held..=held * (bricks - 1)).contains(&falls), "{input}");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::combinator::{int, lines, parse_all, tag, Parser};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{add, Pos3};
+use aoc_common::parallel;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+use aoc_common::trace;
+
+mod generate;
+
+type Pos = Pos3<i16>;
+
+#[derive(Debug, Clone)]
+pub struct Brick {
+    /// Place of the brick in the input, which stays the same when bricks
+    /// are removed.
+    id: usize,
+    cubes: Vec<Pos>,
+}
+
+/// A position like `1,0,1`.
+fn pos<'a>() -> impl Parser<'a, Pos> {
+    int()
+        .skip(tag(","))
+        .then(int())
+        .skip(tag(","))
+        .then(int())
+        .map(|((x, y), z)| (x, y, z))
+}
+
+/// The ends of a brick like `1,0,1~1,2,1`, which only differ along one axis
+/// and sit above the ground.
+fn ends<'a>() -> impl Parser<'a, (Pos, Pos)> {
+    pos()
+        .skip(tag("~"))
+        .then(pos())
+        .with_text()
+        .and_then(|(text, (a, b))| {
+            let axes = [a.0 != b.0, a.1 != b.1, a.2 != b.2];
+            if axes.iter().filter(|&&d| d).count() > 1 {
+                Err(ParseError::new("a brick along a single axis", text))
+            } else if a.2.min(b.2) < 1 {
+                Err(ParseError::new("a brick above the ground", text))
+            } else {
+                Ok((a, b))
+            }
+        })
+}
+
+impl Brick {
+    /// Brick `id` of the input, with the cubes between the ends `a` and `b`,
+    /// both included.
+    fn new(id: usize, a: Pos, b: Pos) -> Brick {
+        let cubes = if a.0 != b.0 {
+            let min = a.0.min(b.0);
+            let max = a.0.max(b.0);
+            (min..=max).map(|v| (v, a.1, a.2)).collect()
+        } else if a.1 != b.1 {
+            let min = a.1.min(b.1);
+            let max = a.1.max(b.1);
+            (min..=max).map(|v| (a.0, v, a.2)).collect()
+        } else if a.2 != b.2 {
+            let min = a.2.min(b.2);
+            let max = a.2.max(b.2);
+            (min..=max).map(|v| (a.0, a.1, v)).collect()
+        } else {
+            vec![a]
+        };
+        Brick { id, cubes }
+    }
+}
+
+fn fall(bricks: &mut [Brick]) -> Vec<usize> {
+    let mut moved = vec![];
+    let down = (0, 0, -1);
+    let map: HashMap<Pos, usize> = bricks
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, b)| b.cubes.iter().map(move |c| (*c, idx)))
+        .collect();
+
+    for (idx, b) in bricks.iter_mut().enumerate() {
+        if b.cubes
+            .iter()
+            .all(|&c| c.2 != 1 && map.get(&add(down, c)).copied().unwrap_or(idx) == idx)
+        {
+            moved.push(idx);
+            b.cubes.iter_mut().for_each(|c| *c = add(down, *c));
+            trace!("fall", brick = b.id, to = b.cubes[0]);
+        }
+    }
+
+    moved
+}
+
+/// The bricks after they've all come to rest.
+fn settled(bricks: &[Brick]) -> Vec<Brick> {
+    let mut bricks = bricks.to_vec();
+    let mut steps = 0;
+    while !fall(&mut bricks).is_empty() {
+        steps += 1;
+    }
+    trace!("settle", steps = steps);
+    bricks
+}
+
+fn p1(bricks: &[Brick]) -> usize {
+    let bricks = settled(bricks);
+    let indices: Vec<usize> = (0..bricks.len()).collect();
+
+    let safe = parallel::map(&indices, |&i| {
+        let mut test = bricks.clone();
+        test.swap_remove(i);
+        let safe = fall(&mut test).is_empty();
+        trace!("remove", brick = i, safe = safe);
+        safe
+    });
+
+    safe.into_iter().filter(|&safe| safe).count()
+}
+
+fn p2(bricks: &[Brick]) -> usize {
+    let bricks = settled(bricks);
+    let indices: Vec<usize> = (0..bricks.len()).collect();
+
+    let falling = parallel::map(&indices, |&i| {
+        let mut test = bricks.clone();
+        test.swap_remove(i);
+
+        let mut list = HashSet::new();
+
+        loop {
+            let fell = fall(&mut test);
+            if fell.is_empty() {
+                break;
+            }
+            list.extend(fell);
+        }
+        trace!("remove", brick = i, fell = list.len());
+
+        list.len()
+    });
+
+    falling.into_iter().sum()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const EVENTS: &'static [&'static str] = &["fall", "settle", "remove"];
+    type Model<'a> = Vec<Brick>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let ends = parse_all(lines(ends()), input)?;
+        Ok(ends
+            .into_iter()
+            .enumerate()
+            .map(|(id, (a, b))| Brick::new(id, a, b))
+            .collect())
+    }
+
+    fn part1(bricks: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(bricks).into())
+    }
+
+    fn part2(bricks: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(bricks).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day22::parse(EXAMPLE).unwrap()), 5);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day22::parse(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn bad_bricks() {
+        let input = "1,0,1~1,2,1\n0,0,2~2,1,2\n";
+        let Err(err) = Day22::parse(input) else {
+            panic!("parsed");
+        };
+        assert_eq!(err.expected, "a brick along a single axis");
+        assert_eq!(err.found, "0,0,2~2,1,2");
+
+        let Err(err) = Day22::parse("1,0,0~1,2,0\n") else {
+            panic!("parsed");
+        };
+        assert_eq!(err.expected, "a brick above the ground");
+    }
+}
@@ -0,0 +1,99 @@
+//! Hiking maps like the puzzle's: a lattice of up to 6 by 6 junctions, one
+//! for every 8 tiles of `size`, with trails between neighbours going right
+//! or down past slopes so the slippery hike can't loop. Some trails are left
+//! out, but never all the ways from the start to the finish.
+
+use aoc_common::geom::Pos;
+use aoc_common::rng::Rng;
+
+/// `k` lattice lines from `first` on, 3 to 9 tiles apart.
+fn lines(rng: &mut Rng, k: usize, first: i64) -> Vec<i64> {
+    let mut at = first;
+    (0..k)
+        .map(|i| {
+            if i > 0 {
+                at += rng.range(3..10);
+            }
+            at
+        })
+        .collect()
+}
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let k = (size / 8).clamp(2, 6);
+    let first = rng.range(1..4);
+    let xs = lines(rng, k, first);
+    let first = rng.range(2..5);
+    let ys = lines(rng, k, first);
+    let width = xs[k - 1] + rng.range(2..4);
+    let height = ys[k - 1] + rng.range(3..6);
+
+    // a random walk right and down from the first junction to the last is
+    // always kept
+    let mut keep = vec![];
+    let (mut i, mut j) = (0, 0);
+    while (i, j) != (k - 1, k - 1) {
+        let right = j == k - 1 || (i < k - 1 && rng.chance(1, 2));
+        keep.push(((i, j), right));
+        if right {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let mut map = vec![vec!['#'; width as usize]; height as usize];
+    let mut dig = |(x, y): Pos, c: char| map[y as usize][x as usize] = c;
+    for y in 0..=ys[0] {
+        dig((xs[0], y), '.');
+    }
+    for y in ys[k - 1]..height {
+        dig((xs[k - 1], y), '.');
+    }
+    dig((xs[0], ys[0] - 1), 'v');
+    dig((xs[k - 1], ys[k - 1] + 1), 'v');
+    for j in 0..k {
+        for i in 0..k {
+            for right in [true, false] {
+                let ((x0, y0), (x1, y1)) = match right {
+                    true if i + 1 < k => ((xs[i], ys[j]), (xs[i + 1], ys[j])),
+                    false if j + 1 < k => ((xs[i], ys[j]), (xs[i], ys[j + 1])),
+                    _ => continue,
+                };
+                if !keep.contains(&((i, j), right)) && rng.chance(1, 4) {
+                    continue;
+                }
+                for x in x0..=x1 {
+                    for y in y0..=y1 {
+                        dig((x, y), '.');
+                    }
+                }
+                let slope = if right { '>' } else { 'v' };
+                let (dx, dy) = if right { (1, 0) } else { (0, 1) };
+                dig((x0 + dx, y0 + dy), slope);
+                dig((x1 - dx, y1 - dy), slope);
+            }
+        }
+    }
+
+    map.iter()
+        .flat_map(|r| r.iter().chain(['\n'].iter()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day23;
+
+    /// Climbing the slopes only allows longer hikes, none through more tiles
+    /// than the map has.
+    #[test]
+    fn hike_lengths() {
+        for (input, answers) in solve_generated::<Day23>(0..10, 32) {
+            let tiles = input.matches(['.', '>', 'v']).count() as i128;
+            assert!(answers[0] <= answers[1] && answers[1] < tiles, "{input}");
+        }
+    }
+}
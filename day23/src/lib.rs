@@ -0,0 +1,328 @@
+use aoc_common::combinator::{grid, parse_all};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{neighbours4, Dir, Pos};
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{char_at, end};
+use aoc_common::render::{Canvas, Rgb};
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+/// Most forks the walks of part 2 can keep track of.
+const MAX_JUNCTIONS: usize = 64;
+
+pub struct Trails<'a> {
+    map: Grid<char>,
+    start: Pos,
+    finish: Pos,
+    /// The input, to point at tiles in errors.
+    text: &'a str,
+}
+
+impl<'a> Trails<'a> {
+    fn tile(&self, (x, y): Pos) -> &'a str {
+        let line = self.text.lines().nth(y as usize).unwrap_or_default();
+        char_at(line, x as usize)
+    }
+
+    fn open(&self, p: Pos) -> bool {
+        matches!(self.map.get(p), Some(&c) if c != '#')
+    }
+
+    fn is_junction(&self, p: Pos) -> bool {
+        p == self.start || p == self.finish || neighbours4(p).filter(|&n| self.open(n)).count() > 2
+    }
+}
+
+/// Tiles of the trail leaving junction `from` going `dir`, up to and
+/// including the next junction, if there is one that way. With `slopes`,
+/// trails can only be walked down their slopes.
+fn trail(trails: &Trails, from: Pos, dir: Dir, slopes: bool) -> Option<Vec<Pos>> {
+    let passable = |p: Pos, d: Dir| match trails.map.get(p) {
+        Some('.') => true,
+        Some(&c) if c != '#' => !slopes || Dir::from_char(c) == Some(d),
+        _ => false,
+    };
+
+    let (mut pos, mut d) = (from, dir);
+    let mut tiles = vec![];
+    loop {
+        let next = d.step(pos);
+        if !passable(next, d) {
+            return None;
+        }
+        pos = next;
+        tiles.push(pos);
+        if trails.is_junction(pos) {
+            return Some(tiles);
+        }
+        // follow the trail to its only other open tile
+        d = Dir::ALL
+            .into_iter()
+            .find(|n| *n != d.opposite() && trails.open(n.step(pos)))?;
+    }
+}
+
+/// The start, the finish and every fork in the trails, with the length of
+/// each trail between them.
+fn junctions(trails: &Trails, slopes: bool) -> Graph<Pos> {
+    let mut graph = Graph::directed();
+    let junctions = trails
+        .map
+        .positions()
+        .filter(|&p| trails.open(p) && trails.is_junction(p));
+    for from in junctions {
+        graph.node(from);
+        for dir in Dir::ALL {
+            if let Some(tiles) = trail(trails, from, dir, slopes) {
+                graph.add_weighted_edge(from, *tiles.last().unwrap(), tiles.len() as u64);
+            }
+        }
+    }
+    graph
+}
+
+/// Length of the longest hike down the slopes, and the junctions it passes,
+/// `None` if the slopes don't lead to the finish.
+fn slope_hike(trails: &Trails) -> Option<(u64, Vec<Pos>)> {
+    let graph = junctions(trails, true);
+    let start = graph.id(&trails.start).unwrap();
+    let finish = graph.id(&trails.finish).unwrap();
+
+    // with the slopes there's usually no going back, so the longest walk
+    // can be found junction by junction in order
+    let Some(order) = graph.toposort() else {
+        // else the trails can loop, like when there are no slopes at all
+        let (len, hike) = longest_path(&graph, start, finish, 1 << start)?;
+        let hike = hike.into_iter().rev().map(|id| *graph.label(id));
+        return Some((len, [trails.start].into_iter().chain(hike).collect()));
+    };
+    let mut longest: Vec<Option<(u64, NodeId)>> = vec![None; graph.len()];
+    longest[start] = Some((0, start));
+    for id in order {
+        let Some((d, _)) = longest[id] else {
+            continue;
+        };
+        for &(next, len) in graph.edges(id) {
+            if longest[next].is_none_or(|(best, _)| d + len > best) {
+                longest[next] = Some((d + len, id));
+            }
+        }
+    }
+
+    let (len, _) = longest[finish]?;
+    let mut hike = vec![finish];
+    while let Some(&id) = hike.last().filter(|&&id| id != start) {
+        hike.push(longest[id].unwrap().1);
+    }
+    let hike = hike.into_iter().rev().map(|id| *graph.label(id)).collect();
+    Some((len, hike))
+}
+
+fn p1(trails: &Trails) -> ParseResult<u64> {
+    match slope_hike(trails) {
+        Some((len, _)) => Ok(len),
+        None => Err(ParseError::new(
+            "a way down the slopes to the finish",
+            trails.tile(trails.finish),
+        )),
+    }
+}
+
+fn longest_walk(graph: &Graph<Pos>, id: NodeId, target: NodeId, seen: u64) -> Option<u64> {
+    if id == target {
+        return Some(0);
+    }
+    graph
+        .edges(id)
+        .iter()
+        .filter(|(next, _)| seen & (1 << next) == 0)
+        .filter_map(|&(next, len)| {
+            longest_walk(graph, next, target, seen | (1 << next)).map(|d| d + len)
+        })
+        .max()
+}
+
+/// Like [`longest_walk`], with the junctions it passes after `id` from the
+/// last one, for the slopes that don't keep the walks from looping.
+fn longest_path(
+    graph: &Graph<Pos>,
+    id: NodeId,
+    target: NodeId,
+    seen: u64,
+) -> Option<(u64, Vec<NodeId>)> {
+    if id == target {
+        return Some((0, vec![]));
+    }
+    graph
+        .edges(id)
+        .iter()
+        .filter(|(next, _)| seen & (1 << next) == 0)
+        .filter_map(|&(next, len)| {
+            let (d, mut path) = longest_path(graph, next, target, seen | (1 << next))?;
+            path.push(next);
+            Some((d + len, path))
+        })
+        .max_by_key(|(d, _)| *d)
+}
+
+fn p2(trails: &Trails) -> ParseResult<u64> {
+    // there are at most MAX_JUNCTIONS, checked when parsing
+    let graph = junctions(trails, false);
+    let start = graph.id(&trails.start).unwrap();
+    let finish = graph.id(&trails.finish).unwrap();
+
+    // the last fork before the finish has to go straight to it, or the walk
+    // can't reach the finish without going through the fork again
+    let (target, extra) = match graph.edges(finish) {
+        &[(last, len)] => (last, len),
+        _ => (finish, 0),
+    };
+
+    match longest_walk(&graph, start, target, 1 << start) {
+        Some(len) => Ok(len + extra),
+        None => Err(ParseError::new(
+            "a trail to the finish",
+            trails.tile(trails.finish),
+        )),
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Model<'a> = Trails<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let map = parse_all(grid(".#<>^v"), input)?;
+
+        let last = map.height() - 1;
+        let Some(x) = map.row(0).iter().position(|&c| c == '.') else {
+            let row = input.lines().next().unwrap_or_default();
+            return Err(ParseError::new("an opening in the first row", row));
+        };
+        let start = (x as i64, 0);
+        let Some(x) = map.row(last).iter().position(|&c| c == '.') else {
+            return Err(ParseError::new("an opening in the last row", end(input)));
+        };
+        let finish = (x as i64, last as i64);
+
+        let trails = Trails {
+            map,
+            start,
+            finish,
+            text: input,
+        };
+        let mut forks = trails
+            .map
+            .positions()
+            .filter(|&p| trails.open(p) && trails.is_junction(p));
+        if let Some(p) = forks.nth(MAX_JUNCTIONS) {
+            let expected = format!("at most {MAX_JUNCTIONS} forks");
+            return Err(ParseError::new(expected, trails.tile(p)));
+        }
+        Ok(trails)
+    }
+
+    fn part1(trails: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(trails)?.into())
+    }
+
+    fn part2(trails: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p2(trails)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+
+    fn render(trails: &Self::Model<'_>, _params: &Self::Params) -> Option<Canvas> {
+        // the tiles of the longest trail between each junction and the next
+        let (_, hike) = slope_hike(trails)?;
+        let mut tiles = vec![trails.start];
+        for w in hike.windows(2) {
+            let leg = Dir::ALL
+                .into_iter()
+                .filter_map(|d| trail(trails, w[0], d, true))
+                .filter(|t| t.last() == Some(&w[1]))
+                .max_by_key(|t| t.len())
+                .unwrap();
+            tiles.extend(leg);
+        }
+        Some(Canvas::new(trails.map.clone()).overlay(Rgb::RED, Some('O'), tiles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day23::parse(EXAMPLE).unwrap()), Ok(94));
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day23::parse(EXAMPLE).unwrap()), Ok(154));
+    }
+
+    #[test]
+    fn without_slopes() {
+        let trails = Day23::parse("#.#\n#.#\n#.#\n").unwrap();
+        assert_eq!(slope_hike(&trails), Some((2, vec![(1, 0), (1, 2)])));
+        assert_eq!(p2(&trails), Ok(2));
+    }
+
+    #[test]
+    fn closed_rows() {
+        let input = "###\n#.#\n#.#\n";
+        let Err(err) = Day23::parse(input) else {
+            panic!("parsed");
+        };
+        assert_eq!(
+            err.locate(input).to_string(),
+            "line 1, column 1, expected an opening in the first row, found `###`"
+        );
+    }
+
+    #[test]
+    fn unreachable_finish() {
+        let input = "#.###\n#v#.#\n#.#.#\n#^#.#\n###.#\n";
+        let trails = Day23::parse(input).unwrap();
+        for err in [p1(&trails), p2(&trails)] {
+            let err = err.unwrap_err().locate(input);
+            assert_eq!((err.line, err.column), (Some(5), Some(4)));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Hailstorms of `size` hailstones, at least 5, all hit by one throw of a
+//! rock from somewhere between 100 and 300 trillion along each axis.
+//!
+//! Each hailstone is put where the rock will be when it hits it, then moved
+//! back along its own path to where it is at the start, which keeps its
+//! position whole.
+
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let from = || 100_000_000_000_000..300_000_000_000_001;
+    let rock = [(); 3].map(|_| rng.range(from()));
+    let throw = [(); 3].map(|_| rng.range(-300..301));
+
+    let mut times = vec![];
+    let mut s = String::new();
+    while times.len() < size.max(5) {
+        let t = rng.range(10_000_000_000..1_000_000_000_000);
+        if times.contains(&t) {
+            continue;
+        }
+        // only hailstones moving across the rock's path along x can be told
+        // apart from it
+        let v = [(); 3].map(|_| rng.range(-300..301));
+        if v[0] == throw[0] {
+            continue;
+        }
+        times.push(t);
+        let p: Vec<_> = (0..3).map(|i| rock[i] + t * (throw[i] - v[i])).collect();
+        s.push_str(&format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            p[0], p[1], p[2], v[0], v[1], v[2]
+        ));
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day24;
+
+    /// The rock starts between 100 and 300 trillion along each axis.
+    #[test]
+    fn rock_position() {
+        let trillion = 1_000_000_000_000;
+        for (input, answers) in solve_generated::<Day24>(0..2, 5) {
+            let sums = 300 * trillion..=900 * trillion;
+            assert!(sums.contains(&answers[1]), "{input}");
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use aoc_common::combinator::{int, lines, parse_all, preceded, spaces, tag, Parser};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::geom::{add, Pos3};
+use aoc_common::params;
+use aoc_common::parse::end;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+params! {
+    /// Bounds on X and Y of the test area of part 1, both included.
+    pub struct TestArea {
+        pub min: i64 = 200_000_000_000_000,
+        pub max: i64 = 400_000_000_000_000,
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.min > self.max {
+            return Err("`min` must be at most `max`".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hail {
+    p: Pos3,
+    v: Pos3,
+}
+
+pub struct Hailstorm<'a> {
+    hails: Vec<Hail>,
+    /// End of the input, where errors about the whole hailstorm point.
+    end: &'a str,
+}
+
+/// A position or velocity like `19, 13, 30` or `-2,  1, -2`, where the
+/// numbers can be padded to line up.
+fn pos<'a>() -> impl Parser<'a, Pos3> {
+    let coord = || preceded(spaces().opt(), int());
+    coord()
+        .skip(tag(","))
+        .then(coord())
+        .skip(tag(","))
+        .then(coord())
+        .map(|((x, y), z)| (x, y, z))
+}
+
+/// A hailstone like `19, 13, 30 @ -2,  1, -2`.
+fn hail<'a>() -> impl Parser<'a, Hail> {
+    pos()
+        .skip(tag(" @ "))
+        .then(pos())
+        .map(|(p, v)| Hail { p, v })
+}
+
+impl Hail {
+    fn intersect2(&self, other: &Hail) -> Option<(f64, f64)> {
+        let (x1, y1, _) = self.p;
+        let (x2, y2, _) = add(self.p, self.v);
+
+        let (x3, y3, _) = other.p;
+        let (x4, y4, _) = add(other.p, other.v);
+        let x1 = x1 as i128;
+        let x2 = x2 as i128;
+        let x3 = x3 as i128;
+        let x4 = x4 as i128;
+        let y1 = y1 as i128;
+        let y2 = y2 as i128;
+        let y3 = y3 as i128;
+        let y4 = y4 as i128;
+
+        let denom = ((x1 - x2) * (y3 - y4)) - ((y1 - y2) * (x3 - x4));
+
+        if denom == 0 {
+            return None;
+        }
+
+        let num_x = ((x1 * y2 - y1 * x2) * (x3 - x4)) - ((x1 - x2) * (x3 * y4 - y3 * x4));
+
+        let num_y = ((x1 * y2 - y1 * x2) * (y3 - y4)) - ((y1 - y2) * (x3 * y4 - y3 * x4));
+
+        Some((num_x as f64 / denom as f64, num_y as f64 / denom as f64))
+    }
+
+    fn future(&self, other: &Hail, lim_a: f64, lim_b: f64) -> bool {
+        if let Some((x, y)) = self.intersect2(other) {
+            if x >= lim_a
+                && x <= lim_b
+                && y >= lim_a
+                && y <= lim_b
+                && (x > self.p.0 as f64 && self.v.0 > 0 || x < self.p.0 as f64 && self.v.0 < 0)
+                && (x > other.p.0 as f64 && other.v.0 > 0 || x < other.p.0 as f64 && other.v.0 < 0)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn future_int(&self, other: &Hail, lim_a: f64, lim_b: f64) -> Option<(i64, i64)> {
+        if let Some((x, y)) = self.intersect2(other) {
+            if x >= lim_a
+                && x <= lim_b
+                && y >= lim_a
+                && y <= lim_b
+                && (x > self.p.0 as f64 && self.v.0 > 0 || x < self.p.0 as f64 && self.v.0 < 0)
+                && (x > other.p.0 as f64 && other.v.0 > 0 || x < other.p.0 as f64 && other.v.0 < 0)
+                && (x - x.round()).abs() < 0.1
+                && (y - y.round()).abs() < 0.1
+            {
+                return Some((x.round() as i64, y.round() as i64));
+            }
+        } else {
+            // parallel
+            return Some((0, 0));
+        }
+        None
+    }
+}
+
+/// Pairs whose paths cross in the future inside the `lim_a..=lim_b` square.
+fn crossings(hails: &[Hail], lim_a: f64, lim_b: f64) -> usize {
+    let mut cnt = 0;
+    for (i, a) in hails.iter().enumerate() {
+        for b in &hails[i + 1..] {
+            if a.future(b, lim_a, lim_b) {
+                cnt += 1;
+            }
+        }
+    }
+
+    cnt
+}
+
+fn p1(hails: &[Hail], area: &TestArea) -> usize {
+    crossings(hails, area.min as f64, area.max as f64)
+}
+
+/// Where a rock thrown with a speed under 1000 along X and Y hits all the
+/// `hails`, `None` if no throw does.
+fn brute_force(hails: &[Hail], swap: bool) -> Option<(i64, i64)> {
+    let lim_a = 0.0;
+    let lim_b = 400000000000000.0;
+    for vx in -1000..1000 {
+        'outer: for vy in -1000..1000 {
+            let mut prev = None;
+            for (i, a) in hails.iter().enumerate() {
+                for b in &hails[i + 1..] {
+                    let mut aa = a.clone();
+                    let mut bb = b.clone();
+
+                    if swap {
+                        aa.p = (aa.p.0, aa.p.2, aa.p.2);
+                        bb.p = (bb.p.0, bb.p.2, bb.p.2);
+                        aa.v = (aa.v.0, aa.v.2, aa.v.2);
+                        bb.v = (bb.v.0, bb.v.2, bb.v.2);
+                    }
+
+                    aa.v.0 -= vx;
+                    bb.v.0 -= vx;
+                    aa.v.1 -= vy;
+                    bb.v.1 -= vy;
+
+                    if let Some(p) = aa.future_int(&bb, lim_a, lim_b) {
+                        if p == (0, 0) {
+                            continue;
+                        }
+                        if let Some(pr) = prev {
+                            if pr != p {
+                                continue 'outer;
+                            }
+                        }
+                        prev = Some(p);
+                    } else {
+                        continue 'outer;
+                    }
+                }
+            }
+            // hailstones all parallel to the throw don't tell where it starts
+            if prev.is_some() {
+                return prev;
+            }
+        }
+    }
+    None
+}
+
+fn p2(hails: &[Hail]) -> Option<i64> {
+    let (_, y) = brute_force(hails, false)?;
+    let (x, z) = brute_force(hails, true)?;
+    Some(x + y + z)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Model<'a> = Hailstorm<'a>;
+    type Params = TestArea;
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        Ok(Hailstorm {
+            hails: parse_all(lines(hail()), input)?,
+            end: end(input),
+        })
+    }
+
+    fn part1(storm: &Self::Model<'_>, area: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(&storm.hails, area).into())
+    }
+
+    fn part2(storm: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        match p2(&storm.hails) {
+            Some(sum) => Ok(sum.into()),
+            None => Err(ParseError::new(
+                "hailstones that one thrown rock can all hit",
+                storm.end,
+            )),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn part1() {
+        let area = TestArea { min: 7, max: 27 };
+        assert_eq!(p1(&Day24::parse(EXAMPLE).unwrap().hails, &area), 2);
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(p2(&Day24::parse(EXAMPLE).unwrap().hails), Some(47));
+    }
+
+    #[test]
+    fn no_throw() {
+        // one hailstone, or three no rock lines up with
+        let one = &EXAMPLE[..EXAMPLE.find('\n').unwrap() + 1];
+        let missed = "0, 0, 0 @ 1, 0, 0\n0, 10, 0 @ 0, 1, 0\n5, 5, 5 @ 0, 0, 1\n";
+        for input in [one, missed] {
+            let storm = Day24::parse(input).unwrap();
+            let err = Day24::part2(&storm, &TestArea::default()).unwrap_err();
+            assert_eq!(
+                err.locate(input).to_string(),
+                format!(
+                    "line {}, column 1, expected hailstones that one thrown rock can all hit, found end of input",
+                    input.lines().count() + 1
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Wiring diagrams of `size` components, at least 10, in two groups joined
+//! by three wires.
+//!
+//! Each group is a ring with every component also wired to the one two
+//! along, plus a few random wires, so it takes at least four cuts to split
+//! and the three wires between the groups are the only way in three.
+
+use std::collections::{BTreeMap, HashSet};
+
+use aoc_common::generate::name;
+use aoc_common::rng::Rng;
+
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(10);
+    let mut used = HashSet::new();
+    let names: Vec<_> = (0..size).map(|_| name(rng, &mut used, 3)).collect();
+    let split = rng.index(5..size - 4);
+    let groups = [&names[..split], &names[split..]];
+
+    let mut wires = HashSet::new();
+    let mut wire = |a: &String, b: &String| {
+        if a != b && !wires.contains(&(b.clone(), a.clone())) {
+            wires.insert((a.clone(), b.clone()));
+        }
+    };
+    for group in groups {
+        let n = group.len();
+        for i in 0..n {
+            wire(&group[i], &group[(i + 1) % n]);
+            wire(&group[i], &group[(i + 2) % n]);
+        }
+        for _ in 0..n / 2 {
+            wire(rng.pick(group), rng.pick(group));
+        }
+    }
+    let mut ends = groups.map(|g| g.to_vec());
+    for g in &mut ends {
+        rng.shuffle(g);
+    }
+    for (a, b) in ends[0].iter().zip(&ends[1]).take(3) {
+        wire(a, b);
+    }
+
+    // each wire listed under one of its ends
+    let mut wires: Vec<_> = wires.into_iter().collect();
+    wires.sort();
+    let mut lists: BTreeMap<&String, Vec<&String>> = BTreeMap::new();
+    for (a, b) in &wires {
+        let (from, to) = if rng.chance(1, 2) { (a, b) } else { (b, a) };
+        lists.entry(from).or_default().push(to);
+    }
+    let mut lines: Vec<_> = lists
+        .into_iter()
+        .map(|(from, to)| {
+            let to: Vec<_> = to.iter().map(|s| s.as_str()).collect();
+            format!("{from}: {}\n", to.join(" "))
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use aoc_common::solution::solve_generated;
+
+    use crate::Day25;
+
+    /// The three wires join two groups of at least 5 components.
+    #[test]
+    fn two_groups() {
+        for (input, answers) in solve_generated::<Day25>(0..20, 40) {
+            let components: HashSet<_> = input
+                .split([':', ' ', '\n'])
+                .filter(|c| !c.is_empty())
+                .collect();
+            let n = components.len() as i128;
+            assert!((5..=n - 5).any(|a| a * (n - a) == answers[0]), "{input}");
+        }
+    }
+}
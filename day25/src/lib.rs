@@ -0,0 +1,98 @@
+use aoc_common::combinator::{lines, parse_all, spaces, tag, word, Parser};
+use aoc_common::error::{ParseError, ParseResult};
+use aoc_common::graph::{Cut, Graph};
+use aoc_common::parse::end;
+use aoc_common::rng::Rng;
+use aoc_common::solution::{Answer, Solution};
+
+mod generate;
+
+pub struct Wiring<'a> {
+    graph: Graph<&'a str>,
+    /// End of the input, where errors about the whole wiring point.
+    end: &'a str,
+}
+
+/// The three wires splitting the components in two groups, `None` if it
+/// takes fewer or more.
+fn three_wires(wiring: &Wiring) -> Option<Cut> {
+    wiring.graph.global_min_cut().filter(|cut| cut.weight == 3)
+}
+
+fn p1(wiring: &Wiring) -> ParseResult<usize> {
+    let Some(cut) = three_wires(wiring) else {
+        return Err(ParseError::new(
+            "components split in two groups by three wires",
+            wiring.end,
+        ));
+    };
+    Ok(cut.side.len() * (wiring.graph.len() - cut.side.len()))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const PARTS: u8 = 1;
+    type Model<'a> = Wiring<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> ParseResult<Self::Model<'_>> {
+        let line = word().skip(tag(": ")).then(word().sep_by(spaces()));
+        let mut graph = Graph::undirected();
+        for (name, cons) in parse_all(lines(line), input)? {
+            for con in cons {
+                graph.add_edge(name, con);
+            }
+        }
+        Ok(Wiring {
+            graph,
+            end: end(input),
+        })
+    }
+
+    fn part1(wiring: &Self::Model<'_>, _params: &Self::Params) -> ParseResult<Answer> {
+        Ok(p1(wiring)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate::input(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn part1() {
+        assert_eq!(p1(&Day25::parse(EXAMPLE).unwrap()), Ok(54));
+    }
+
+    #[test]
+    fn not_three_wires() {
+        for input in ["a: b\n", "a: b c d e\nb: c d e\nc: d e\nd: e\n"] {
+            let err = p1(&Day25::parse(input).unwrap()).unwrap_err();
+            assert_eq!(
+                err.expected,
+                "components split in two groups by three wires"
+            );
+        }
+    }
+}